@@ -1 +1,4 @@
-pub const ANCHOR_DISCRIMINATOR: usize = 8;
\ No newline at end of file
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+pub const MAX_ATTESTATION_DATA_LEN: usize = 256;
+pub const DEFAULT_REVOCATION_RETENTION_SECS: i64 = 30 * 24 * 60 * 60;
+pub const MAX_PROOF_AGE_SECS: i64 = 10 * 60;
@@ -52,4 +52,121 @@ pub enum AttestationRegistryError {
 
     #[msg("Invalid Public Key")]
     InvalidPublicKey,
+
+    #[msg("Issuer is not authorized for this schema")]
+    IssuerSchemaNotAuthorized,
+
+    #[msg("Issuer is already authorized for this schema")]
+    IssuerSchemaAlreadyAllowed,
+
+    #[msg("Schema is deprecated")]
+    SchemaDeprecated,
+
+    #[msg("Schema does not allow revocation")]
+    SchemaNotRevocable,
+
+    #[msg("Invalid schema name")]
+    InvalidSchemaName,
+
+    #[msg("Attestation data exceeds the schema's maximum length")]
+    DataTooLarge,
+
+    #[msg("Invalid attestation batch")]
+    InvalidBatch,
+
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Revoked by an issuer-wide revocation")]
+    RevokedByIssuerEpoch,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Schema requires the subject to co-sign the attestation")]
+    SubjectConsentRequired,
+
+    #[msg("Attestation is neither expired nor past its revocation retention window")]
+    AttestationStillActive,
+
+    #[msg("Invalid quorum policy")]
+    InvalidQuorum,
+
+    #[msg("Invalid attestation account")]
+    InvalidAttestation,
+
+    #[msg("Signing key is retired or outside its validity window")]
+    SigningKeyInactive,
+
+    #[msg("Invalid signing key validity window")]
+    InvalidKeyWindow,
+
+    #[msg("Bond mint does not match the configured bond mint")]
+    InvalidBondMint,
+
+    #[msg("Bond is below the configured minimum")]
+    InsufficientBond,
+
+    #[msg("Issuer application is not pending review")]
+    ApplicationNotPending,
+
+    #[msg("Issuer application has not been approved")]
+    ApplicationNotApproved,
+
+    #[msg("Invalid issuer application metadata")]
+    InvalidMetadata,
+
+    #[msg("Slash amount exceeds the remaining bond")]
+    SlashExceedsBond,
+
+    #[msg("Invalid Groth16 verifying key")]
+    InvalidVerifyingKey,
+
+    #[msg("Public inputs do not match the verifying key")]
+    InvalidPublicInputs,
+
+    #[msg("Groth16 proof verification failed")]
+    InvalidProof,
+
+    #[msg("Proof is not bound to the posting subject")]
+    ProofSubjectMismatch,
+
+    #[msg("Unauthorized: must be guardian or admin")]
+    NotGuardian,
+
+    #[msg("Attestation keeps history; pass its AttestationHistory account")]
+    HistoryAccountRequired,
+
+    #[msg("Attestation history is already enabled")]
+    HistoryAlreadyEnabled,
+
+    #[msg("Expiry is shorter than the schema minimum")]
+    ExpiryTooSoon,
+
+    #[msg("Signed with a retired issuer key")]
+    RevokedBySigningKey,
+
+    #[msg("Signing key account doesn't match the attestation")]
+    SigningKeyMismatch,
+
+    #[msg("Schema still has open attestations")]
+    SchemaInUse,
+
+    #[msg("Verifying key is missing some of its public input points")]
+    VerifyingKeyIncomplete,
+
+    #[msg("Proof timestamp is too old or predates the current attestation")]
+    StaleProof,
+
+    #[msg("Issuer is still registered; remove it before releasing the bond")]
+    IssuerStillRegistered,
+
+    #[msg("Something was already closed at this address; a batch leaf can only be materialized once")]
+    LeafAlreadyMaterialized,
+
+    #[msg("Leaf was materialized and its attestation has since been revoked or closed")]
+    LeafRevoked,
+
+    #[msg("Bond hasn't been released by the admin yet")]
+    BondNotReleased,
 }
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::SchemaType;
+use crate::state::{IssuerType, KeyType, PauseFlags, QuorumMode, RevocationReason};
 
 #[event]
 pub struct ConfigInitialized {
@@ -18,37 +18,127 @@ pub struct IssuerRemoved {
     pub issuer: Pubkey,
 }
 
+#[event]
+pub struct IssuerKeyAdded {
+    pub issuer: Pubkey,
+    pub key_id: u16,
+    pub key_type: KeyType,
+    pub valid_from: i64,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct IssuerKeyRotated {
+    pub issuer: Pubkey,
+    pub old_key_id: u16,
+    pub new_key_id: u16,
+    pub old_valid_until: i64,
+}
+
+#[event]
+pub struct IssuerKeyRetired {
+    pub issuer: Pubkey,
+    pub key_id: u16,
+}
+
 #[event]
 pub struct IssuerStatusChanged {
     pub issuer: Pubkey,
     pub enabled: bool,
 }
 
+#[event]
+pub struct IssuerSchemaAllowed {
+    pub issuer: Pubkey,
+    pub schema_id: u16,
+}
+
+#[event]
+pub struct IssuerSchemaDisallowed {
+    pub issuer: Pubkey,
+    pub schema_id: u16,
+}
+
 #[event]
 pub struct SchemaAdded {
-    pub schema: SchemaType,
+    pub schema_id: u16,
+    pub name: String,
+    pub version: u16,
+}
+
+#[event]
+pub struct SchemaQuorumSet {
+    pub schema_id: u16,
+    pub mode: QuorumMode,
+    pub threshold: u8,
+    pub issuers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct SchemaDeprecated {
+    pub schema_id: u16,
+}
+
+#[event]
+pub struct SchemaRemoved {
+    pub schema_id: u16,
 }
 
 #[event]
 pub struct AttestationPosted {
     pub subject: Pubkey,
-    pub schema_id: SchemaType,
+    pub schema_id: u16,
     pub issuer: Pubkey,
     pub claim_hash: [u8; 32],
     pub expiry_ts: i64,
+    pub version: u32,
+    pub data: Vec<u8>,
+}
+
+#[event]
+pub struct AttestationBatchPosted {
+    pub issuer: Pubkey,
+    pub batch_id: u64,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u32,
+}
+
+#[event]
+pub struct AttestationMaterialized {
+    pub subject: Pubkey,
+    pub schema_id: u16,
+    pub issuer: Pubkey,
+    pub batch_id: u64,
 }
 
 #[event]
 pub struct AttestationRevoked {
     pub subject: Pubkey,
-    pub schema_id: SchemaType,
+    pub schema_id: u16,
+    pub issuer: Pubkey,
+    pub reason: RevocationReason,
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct IssuerAttestationsRevoked {
+    pub issuer: Pubkey,
+    pub revocation_epoch: u32,
+    pub reason: RevocationReason,
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct AttestationClosed {
+    pub subject: Pubkey,
+    pub schema_id: u16,
     pub issuer: Pubkey,
 }
 
 #[event]
 pub struct AttestationExpiryUpdated {
     pub subject: Pubkey,
-    pub schema_id: SchemaType,
+    pub schema_id: u16,
     pub new_expiry_ts: i64,
 }
 
@@ -60,10 +150,92 @@ pub struct AdminChanged {
 
 #[event]
 pub struct PauseChanged {
-    pub paused: bool,
+    pub paused: PauseFlags,
+    pub changed_by: Pubkey,
+}
+
+#[event]
+pub struct GuardianChanged {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
 }
 
 #[event]
 pub struct MaxExpiryChanged {
     pub max_expiry_secs: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct RevocationRetentionChanged {
+    pub revocation_retention_secs: i64,
+}
+
+#[event]
+pub struct IssuerBondChanged {
+    pub bond_mint: Pubkey,
+    pub min_issuer_bond: u64,
+}
+
+#[event]
+pub struct IssuerApplicationSubmitted {
+    pub issuer: Pubkey,
+    pub applicant: Pubkey,
+    pub issuer_type: IssuerType,
+    pub bond_amount: u64,
+}
+
+#[event]
+pub struct IssuerApplicationApproved {
+    pub issuer: Pubkey,
+    pub applicant: Pubkey,
+}
+
+#[event]
+pub struct IssuerApplicationRejected {
+    pub issuer: Pubkey,
+    pub applicant: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct IssuerApplicationWithdrawn {
+    pub issuer: Pubkey,
+    pub applicant: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct IssuerBondSlashed {
+    pub issuer: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct IssuerBondReleased {
+    pub issuer: Pubkey,
+    pub applicant: Pubkey,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct SchemaVerifyingKeySet {
+    pub schema_id: u16,
+    pub public_inputs: u8, // IC points written so far
+    pub complete: bool,
+}
+
+#[event]
+pub struct AttestationHistoryEnabled {
+    pub attestation: Pubkey,
+    pub subject: Pubkey,
+    pub schema_id: u16,
+}
+
+#[event]
+pub struct SchemaExpiryBoundsSet {
+    pub schema_id: u16,
+    pub min_expiry_secs: i64,
+    pub default_expiry_secs: i64,
+    pub max_expiry_secs: i64,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use solana_bn254::prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing};
+
+use crate::{error::AttestationRegistryError, state::SchemaVerifyingKey};
+
+// BN254 base field modulus, big-endian
+const BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+    0x5d, 0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c,
+    0xfd, 0x47,
+];
+
+// BN254 scalar field modulus, big-endian; public inputs must be reduced below it
+const SCALAR_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+    0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00,
+    0x00, 0x01,
+];
+
+pub struct Groth16Proof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+/*
+    Checks e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1, where
+    vk_x = IC[0] + sum(input_i * IC[i + 1]). Everything runs through the
+    alt_bn128 syscalls, so a full verification costs roughly 4 pairings plus
+    one scalar multiplication per public input.
+*/
+pub fn verify_proof(
+    vk: &SchemaVerifyingKey,
+    proof: &Groth16Proof,
+    public_inputs: &[[u8; 32]],
+) -> Result<()> {
+    require!(
+        !vk.ic.is_empty() && public_inputs.len() == vk.public_input_count(),
+        AttestationRegistryError::InvalidPublicInputs
+    );
+
+    let mut vk_x = vk.ic[0];
+    for (input, point) in public_inputs.iter().zip(&vk.ic[1..]) {
+        require!(
+            *input < SCALAR_FIELD_MODULUS,
+            AttestationRegistryError::InvalidPublicInputs
+        );
+
+        let product = alt_bn128_multiplication(&[point.as_slice(), input].concat())
+            .map_err(|_| AttestationRegistryError::InvalidProof)?;
+        let sum = alt_bn128_addition(&[vk_x.as_slice(), &product].concat())
+            .map_err(|_| AttestationRegistryError::InvalidProof)?;
+        vk_x.copy_from_slice(&sum);
+    }
+
+    let pairing_input = [
+        negate_g1(&proof.a)?.as_slice(),
+        &proof.b,
+        &vk.alpha_g1,
+        &vk.beta_g2,
+        &vk_x,
+        &vk.gamma_g2,
+        &proof.c,
+        &vk.delta_g2,
+    ]
+    .concat();
+
+    let result = alt_bn128_pairing(&pairing_input)
+        .map_err(|_| AttestationRegistryError::InvalidProof)?;
+
+    require!(
+        result.len() == 32 && result[..31].iter().all(|b| *b == 0) && result[31] == 1,
+        AttestationRegistryError::InvalidProof
+    );
+
+    Ok(())
+}
+
+/// (x, y) -> (x, p - y); the point at infinity is its own negation
+fn negate_g1(point: &[u8; 64]) -> Result<[u8; 64]> {
+    let mut negated = *point;
+    let y = &point[32..];
+
+    if y.iter().all(|b| *b == 0) {
+        return Ok(negated);
+    }
+    require!(
+        y < BASE_FIELD_MODULUS.as_slice(),
+        AttestationRegistryError::InvalidProof
+    );
+
+    let mut borrow = 0u16;
+    for i in (0..32).rev() {
+        let diff = BASE_FIELD_MODULUS[i] as i16 - y[i] as i16 - borrow as i16;
+        if diff < 0 {
+            negated[32 + i] = (diff + 256) as u8;
+            borrow = 1;
+        } else {
+            negated[32 + i] = diff as u8;
+            borrow = 0;
+        }
+    }
+
+    Ok(negated)
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::AttestationClosed,
+    state::{Attestation, AttestationHistory, Config, SchemaDefinition},
+};
+
+#[derive(Accounts)]
+pub struct CloseExpiredAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            b"attest",
+            attestation.subject.as_ref(),
+            &attestation.schema_id.to_le_bytes(),
+            attestation.issuer.as_ref()
+        ],
+        bump = attestation.bump,
+        has_one = payer
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [b"schema".as_ref(), &attestation.schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    /// CHECK: the `IssuerSigningKey` recorded on the attestation, checked in
+    /// `Attestation::signing_key_retired_at`; any account for proof-backed attestations
+    pub signing_key: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"attest_history", attestation.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Option<Account<'info, AttestationHistory>>,
+
+    /// CHECK: original rent payer recorded on the attestation; only receives lamports
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/*
+    Permissionless cleanup: anyone can crank this once an attestation has expired,
+    or has been revoked (individually, in bulk or by retiring its signing key) for
+    longer than the configured retention window. The attestation's tombstone stays
+    behind, so a closed version can't be re-posted or re-materialized. Rent always
+    goes back to whoever paid for the account, never to the caller.
+*/
+impl<'info> CloseExpiredAttestation<'info> {
+    pub fn close_expired_attestation(&mut self) -> Result<()> {
+        let config = &self.config;
+        let attestation = &self.attestation;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!config.paused.revocation, AttestationRegistryError::Paused);
+        let key_retired_at = attestation.signing_key_retired_at(&self.signing_key)?;
+        require!(
+            attestation.is_closable(config, key_retired_at, now),
+            AttestationRegistryError::AttestationStillActive
+        );
+
+        self.attestation.archive(self.history.as_mut(), now)?;
+        self.schema.track_closed()?;
+
+        emit!(AttestationClosed {
+            subject: attestation.subject,
+            schema_id: attestation.schema_id,
+            issuer: attestation.issuer,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::AttestationHistoryEnabled,
+    state::{Attestation, AttestationHistory, Config},
+    ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+pub struct EnableAttestationHistory<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"attest",
+            attestation.subject.as_ref(),
+            &attestation.schema_id.to_le_bytes(),
+            attestation.issuer.as_ref()
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + AttestationHistory::INIT_SPACE,
+        seeds = [b"attest_history", attestation.key().as_ref()],
+        bump
+    )]
+    pub history: Account<'info, AttestationHistory>,
+
+    // Subject or admin; enabling adds an account every later re-post must pass
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EnableAttestationHistory<'info> {
+    /// Opts an attestation into keeping its past versions. Only versions
+    /// replaced after this point are recorded. A history left behind by an
+    /// attestation closed at the same address is picked up where it stopped.
+    pub fn enable_attestation_history(&mut self, bump: u8) -> Result<()> {
+        let attestation = &mut self.attestation;
+        let authority = self.authority.key();
+
+        require!(
+            authority == attestation.subject || authority == self.config.admin,
+            AttestationRegistryError::Unauthorized
+        );
+        require!(
+            !attestation.history_enabled,
+            AttestationRegistryError::HistoryAlreadyEnabled
+        );
+
+        attestation.history_enabled = true;
+
+        if self.history.attestation == Pubkey::default() {
+            self.history.set_inner(AttestationHistory {
+                attestation: attestation.key(),
+                head: 0,
+                bump,
+                entries: Vec::new(),
+            });
+        }
+
+        emit!(AttestationHistoryEnabled {
+            attestation: attestation.key(),
+            subject: attestation.subject,
+            schema_id: attestation.schema_id,
+        });
+
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{state::Config, ConfigInitialized, SchemaType, ANCHOR_DISCRIMINATOR};
+use crate::{
+    error::AttestationRegistryError,
+    state::{Config, PauseFlags},
+    ConfigInitialized, ANCHOR_DISCRIMINATOR, DEFAULT_REVOCATION_RETENTION_SECS,
+};
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
@@ -22,17 +26,15 @@ pub struct InitializeConfig<'info> {
 impl<'info> InitializeConfig<'info> {
     /// Initialize the attestation registry configuration
     pub fn initialize_config(&mut self, max_expiry_secs: i64, bump: u8) -> Result<()> {
+        require!(max_expiry_secs > 0, AttestationRegistryError::InvalidExpiry);
+
         let config: &mut Account<'info, Config> = &mut self.config;
         config.admin = self.admin.key();
-        config.max_expiry_secs = max_expiry_secs; // How long attestations can last
-        config.paused = false;
+        config.max_expiry_secs = max_expiry_secs; // Ceiling for per-schema expiry bounds
+        config.paused = PauseFlags::default();
+        config.guardian = Pubkey::default();
         config.bump = bump;
-
-        config.schemas.push(SchemaType::AnonAadhaar);
-        config.schemas.push(SchemaType::ZkPassIdentity);
-        config.schemas.push(SchemaType::Uniqueness);
-        config.schemas.push(SchemaType::CreditKarmaScore);
-        config.schemas.push(SchemaType::PlaidIncome);
+        config.revocation_retention_secs = DEFAULT_REVOCATION_RETENTION_SECS;
 
         emit!(ConfigInitialized {
             admin: config.admin,
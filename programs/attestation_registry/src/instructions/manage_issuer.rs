@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    error::AttestationRegistryError, event::{IssuerAdded, IssuerRemoved, IssuerStatusChanged}, state::{Config, Issuer}, IssuerType,
+    error::AttestationRegistryError,
+    event::{
+        IssuerAdded, IssuerAttestationsRevoked, IssuerRemoved, IssuerSchemaAllowed,
+        IssuerSchemaDisallowed, IssuerStatusChanged,
+    },
+    state::{Config, Issuer, RevocationReason},
+    IssuerType,
 };
 
 #[derive(Accounts)]
@@ -21,21 +27,9 @@ impl<'info> ManageIssuer<'info> {
     pub fn add_issuer(&mut self, issuer: Pubkey, issuer_type: IssuerType) -> Result<()> {
         let config = &mut self.config;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
-        require!(
-            !config.issuers.iter().any(|i| i.pubkey == issuer),
-            AttestationRegistryError::IssuerAlreadyExists
-        );
-        require!(
-            config.issuers.len() < Config::MAX_ISSUERS,
-            AttestationRegistryError::TooManyIssuers
-        );
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
 
-        config.issuers.push(Issuer {
-            pubkey: issuer,
-            issuer_type,
-            enabled: true,
-        });
+        config.register_issuer(issuer, issuer_type)?;
 
         emit!(IssuerAdded { issuer });
 
@@ -45,8 +39,6 @@ impl<'info> ManageIssuer<'info> {
     pub fn remove_issuer(&mut self, issuer: Pubkey) -> Result<()> {
         let config = &mut self.config;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
-
         let pos = config
             .issuers
             .iter()
@@ -63,8 +55,6 @@ impl<'info> ManageIssuer<'info> {
     pub fn set_issuer_status(&mut self, issuer: Pubkey, enabled: bool) -> Result<()> {
         let config = &mut self.config;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
-
         let issuer_entry = config
             .issuers
             .iter_mut()
@@ -77,4 +67,88 @@ impl<'info> ManageIssuer<'info> {
 
         Ok(())
     }
+
+    pub fn allow_issuer_schema(&mut self, issuer: Pubkey, schema_id: u16) -> Result<()> {
+        let config = &mut self.config;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            config.schemas.contains(&schema_id),
+            AttestationRegistryError::SchemaNotFound
+        );
+
+        let issuer_entry = config
+            .issuers
+            .iter_mut()
+            .find(|i| i.pubkey == issuer)
+            .ok_or(AttestationRegistryError::IssuerNotFound)?;
+
+        require!(
+            !issuer_entry.is_schema_allowed(schema_id),
+            AttestationRegistryError::IssuerSchemaAlreadyAllowed
+        );
+        require!(
+            issuer_entry.allowed_schemas.len() < Issuer::MAX_ALLOWED_SCHEMAS,
+            AttestationRegistryError::TooManySchemas
+        );
+
+        issuer_entry.allowed_schemas.push(schema_id);
+
+        emit!(IssuerSchemaAllowed { issuer, schema_id });
+
+        Ok(())
+    }
+
+    pub fn disallow_issuer_schema(&mut self, issuer: Pubkey, schema_id: u16) -> Result<()> {
+        let config = &mut self.config;
+
+        let issuer_entry = config
+            .issuers
+            .iter_mut()
+            .find(|i| i.pubkey == issuer)
+            .ok_or(AttestationRegistryError::IssuerNotFound)?;
+
+        let pos = issuer_entry
+            .allowed_schemas
+            .iter()
+            .position(|s| *s == schema_id)
+            .ok_or(AttestationRegistryError::IssuerSchemaNotAuthorized)?;
+
+        issuer_entry.allowed_schemas.remove(pos);
+
+        emit!(IssuerSchemaDisallowed { issuer, schema_id });
+
+        Ok(())
+    }
+
+    /// Revokes everything the issuer has posted so far, e.g. after a key compromise
+    pub fn revoke_issuer_attestations(
+        &mut self,
+        issuer: Pubkey,
+        reason: RevocationReason,
+    ) -> Result<()> {
+        let config = &mut self.config;
+        let now = Clock::get()?.unix_timestamp;
+
+        let issuer_entry = config
+            .issuers
+            .iter_mut()
+            .find(|i| i.pubkey == issuer)
+            .ok_or(AttestationRegistryError::IssuerNotFound)?;
+
+        issuer_entry.revocation_epoch = issuer_entry
+            .revocation_epoch
+            .checked_add(1)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+        issuer_entry.revoked_at = now;
+
+        emit!(IssuerAttestationsRevoked {
+            issuer,
+            revocation_epoch: issuer_entry.revocation_epoch,
+            reason,
+            revoked_at: now,
+        });
+
+        Ok(())
+    }
 }
@@ -0,0 +1,503 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Transfer};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::AttestationRegistryError,
+    event::{
+        IssuerAdded, IssuerApplicationApproved, IssuerApplicationRejected,
+        IssuerApplicationSubmitted, IssuerApplicationWithdrawn, IssuerBondReleased,
+        IssuerBondSlashed,
+    },
+    state::{ApplicationStatus, Config, IssuerApplication},
+    IssuerType, ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+#[instruction(issuer: Pubkey)]
+pub struct SubmitIssuerApplication<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+
+    #[account(
+        init,
+        payer = applicant,
+        space = ANCHOR_DISCRIMINATOR + IssuerApplication::INIT_SPACE,
+        seeds = [b"issuer_application", applicant.key().as_ref(), issuer.as_ref()],
+        bump
+    )]
+    pub application: Account<'info, IssuerApplication>,
+
+    #[account(
+        address = config.bond_mint @ AttestationRegistryError::InvalidBondMint
+    )]
+    pub bond_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = bond_mint,
+        token::authority = applicant
+    )]
+    pub applicant_token: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = applicant,
+        associated_token::mint = bond_mint,
+        associated_token::authority = application
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitIssuerApplication<'info> {
+    /// Opens an application and locks the bond in a vault owned by the application PDA
+    pub fn submit_issuer_application(
+        &mut self,
+        issuer: Pubkey,
+        issuer_type: IssuerType,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
+        bond_amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let config = &self.config;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            !config.issuers.iter().any(|i| i.pubkey == issuer),
+            AttestationRegistryError::IssuerAlreadyExists
+        );
+        require!(
+            !metadata_uri.is_empty()
+                && metadata_uri.len() <= IssuerApplication::MAX_METADATA_URI_LEN,
+            AttestationRegistryError::InvalidMetadata
+        );
+        require!(
+            bond_amount > 0 && bond_amount >= config.min_issuer_bond,
+            AttestationRegistryError::InsufficientBond
+        );
+
+        let cpi_accounts = Transfer {
+            from: self.applicant_token.to_account_info(),
+            to: self.bond_vault.to_account_info(),
+            authority: self.applicant.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, bond_amount)?;
+
+        self.application.set_inner(IssuerApplication {
+            applicant: self.applicant.key(),
+            issuer,
+            issuer_type,
+            metadata_uri,
+            metadata_hash,
+            bond_mint: self.bond_mint.key(),
+            bond_amount,
+            slashed_amount: 0,
+            status: ApplicationStatus::Pending,
+            submitted_at: Clock::get()?.unix_timestamp,
+            reviewed_at: 0,
+            bump,
+        });
+
+        emit!(IssuerApplicationSubmitted {
+            issuer,
+            applicant: self.applicant.key(),
+            issuer_type,
+            bond_amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ApproveIssuerApplication<'info> {
+    #[account(
+        mut,
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_application",
+            application.applicant.as_ref(),
+            application.issuer.as_ref()
+        ],
+        bump = application.bump
+    )]
+    pub application: Account<'info, IssuerApplication>,
+}
+
+impl<'info> ApproveIssuerApplication<'info> {
+    /// Registers the applicant as an enabled issuer; schemas still have to be allowed separately
+    pub fn approve_issuer_application(&mut self) -> Result<()> {
+        let config = &mut self.config;
+        let application = &mut self.application;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            application.status == ApplicationStatus::Pending,
+            AttestationRegistryError::ApplicationNotPending
+        );
+
+        config.register_issuer(application.issuer, application.issuer_type)?;
+
+        application.status = ApplicationStatus::Approved;
+        application.reviewed_at = Clock::get()?.unix_timestamp;
+
+        emit!(IssuerAdded {
+            issuer: application.issuer
+        });
+        emit!(IssuerApplicationApproved {
+            issuer: application.issuer,
+            applicant: application.applicant,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RejectIssuerApplication<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: receives the application and vault rent, pinned by `has_one`
+    #[account(mut)]
+    pub applicant: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_application",
+            application.applicant.as_ref(),
+            application.issuer.as_ref()
+        ],
+        bump = application.bump,
+        has_one = applicant,
+        has_one = bond_mint,
+        close = applicant
+    )]
+    pub application: Account<'info, IssuerApplication>,
+
+    pub bond_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = bond_mint,
+        token::authority = applicant
+    )]
+    pub applicant_token: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = application
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RejectIssuerApplication<'info> {
+    /// Refunds the full bond and closes the application so the issuer can reapply
+    pub fn reject_issuer_application(&mut self) -> Result<()> {
+        require!(
+            self.application.status == ApplicationStatus::Pending,
+            AttestationRegistryError::ApplicationNotPending
+        );
+
+        let refunded = refund_bond(
+            &self.application,
+            &self.bond_vault,
+            &self.applicant_token,
+            &self.applicant.to_account_info(),
+            &self.token_program,
+        )?;
+
+        emit!(IssuerApplicationRejected {
+            issuer: self.application.issuer,
+            applicant: self.applicant.key(),
+            refunded,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SlashIssuerBond<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_application",
+            application.applicant.as_ref(),
+            application.issuer.as_ref()
+        ],
+        bump = application.bump,
+        has_one = bond_mint
+    )]
+    pub application: Account<'info, IssuerApplication>,
+
+    pub bond_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = application
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    // Where seized funds go, e.g. a treasury or the harmed parties
+    #[account(
+        mut,
+        token::mint = bond_mint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SlashIssuerBond<'info> {
+    /// Seizes part of an approved issuer's bond. Pair with `revoke_issuer_attestations`
+    /// when the issuer's existing attestations should stop verifying too
+    pub fn slash_issuer_bond(&mut self, amount: u64) -> Result<()> {
+        require!(
+            self.application.status == ApplicationStatus::Approved,
+            AttestationRegistryError::ApplicationNotApproved
+        );
+        require!(
+            amount > 0 && amount <= self.application.remaining_bond(),
+            AttestationRegistryError::SlashExceedsBond
+        );
+
+        let issuer = self.application.issuer;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"issuer_application",
+            self.application.applicant.as_ref(),
+            issuer.as_ref(),
+            &[self.application.bump],
+        ]];
+
+        let cpi_accounts = Transfer {
+            from: self.bond_vault.to_account_info(),
+            to: self.destination.to_account_info(),
+            authority: self.application.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer(cpi_ctx, amount)?;
+
+        let application = &mut self.application;
+        application.slashed_amount = application
+            .slashed_amount
+            .checked_add(amount)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+
+        emit!(IssuerBondSlashed {
+            issuer,
+            amount,
+            remaining: application.remaining_bond(),
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReleaseIssuerBond<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_application",
+            application.applicant.as_ref(),
+            application.issuer.as_ref()
+        ],
+        bump = application.bump
+    )]
+    pub application: Account<'info, IssuerApplication>,
+}
+
+impl<'info> ReleaseIssuerBond<'info> {
+    /// Lets the applicant withdraw what is left of the bond once the issuer has been
+    /// removed. Separate from `remove_issuer` so the admin can still slash in between.
+    pub fn release_issuer_bond(&mut self) -> Result<()> {
+        let application = &mut self.application;
+        let issuer = application.issuer;
+
+        require!(
+            application.status == ApplicationStatus::Approved,
+            AttestationRegistryError::ApplicationNotApproved
+        );
+        require!(
+            !self.config.issuers.iter().any(|i| i.pubkey == issuer),
+            AttestationRegistryError::IssuerStillRegistered
+        );
+
+        application.status = ApplicationStatus::Released;
+
+        emit!(IssuerBondReleased {
+            issuer,
+            applicant: application.applicant,
+            remaining: application.remaining_bond(),
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawIssuerApplication<'info> {
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_application",
+            applicant.key().as_ref(),
+            application.issuer.as_ref()
+        ],
+        bump = application.bump,
+        has_one = applicant,
+        has_one = bond_mint,
+        close = applicant
+    )]
+    pub application: Account<'info, IssuerApplication>,
+
+    pub bond_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = bond_mint,
+        token::authority = applicant
+    )]
+    pub applicant_token: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = application
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawIssuerApplication<'info> {
+    /// Returns what is left of the bond and closes the application. A pending application
+    /// can be withdrawn at any time; an approved one only after `release_issuer_bond`.
+    pub fn withdraw_issuer_application(&mut self) -> Result<()> {
+        let issuer = self.application.issuer;
+
+        require!(
+            self.application.status != ApplicationStatus::Approved,
+            AttestationRegistryError::BondNotReleased
+        );
+
+        let refunded = refund_bond(
+            &self.application,
+            &self.bond_vault,
+            &self.applicant_token,
+            &self.applicant.to_account_info(),
+            &self.token_program,
+        )?;
+
+        emit!(IssuerApplicationWithdrawn {
+            issuer,
+            applicant: self.applicant.key(),
+            refunded,
+        });
+
+        Ok(())
+    }
+}
+
+/// Sends everything left in the vault back to the applicant and closes the vault.
+/// Returns the amount refunded.
+fn refund_bond<'info>(
+    application: &Account<'info, IssuerApplication>,
+    bond_vault: &Account<'info, TokenAccount>,
+    applicant_token: &Account<'info, TokenAccount>,
+    applicant: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<u64> {
+    let refunded = bond_vault.amount;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"issuer_application",
+        application.applicant.as_ref(),
+        application.issuer.as_ref(),
+        &[application.bump],
+    ]];
+
+    let cpi_accounts = Transfer {
+        from: bond_vault.to_account_info(),
+        to: applicant_token.to_account_info(),
+        authority: application.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    transfer(cpi_ctx, refunded)?;
+
+    let cpi_accounts = CloseAccount {
+        account: bond_vault.to_account_info(),
+        destination: applicant.clone(),
+        authority: application.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    close_account(cpi_ctx)?;
+
+    Ok(refunded)
+}
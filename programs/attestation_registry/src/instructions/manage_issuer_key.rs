@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::{IssuerKeyAdded, IssuerKeyRetired, IssuerKeyRotated},
+    state::{Config, IssuerSigningKey, KeyType},
+    ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+#[instruction(issuer: Pubkey, key_id: u16)]
+pub struct AddIssuerKey<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + IssuerSigningKey::INIT_SPACE,
+        seeds = [b"issuer_key".as_ref(), issuer.as_ref(), &key_id.to_le_bytes()],
+        bump
+    )]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_key_id: u16)]
+pub struct RotateIssuerKey<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_key".as_ref(),
+            old_key.issuer.as_ref(),
+            &old_key.key_id.to_le_bytes()
+        ],
+        bump = old_key.bump
+    )]
+    pub old_key: Account<'info, IssuerSigningKey>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + IssuerSigningKey::INIT_SPACE,
+        seeds = [b"issuer_key".as_ref(), old_key.issuer.as_ref(), &new_key_id.to_le_bytes()],
+        bump
+    )]
+    pub new_key: Account<'info, IssuerSigningKey>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RetireIssuerKey<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer_key".as_ref(),
+            signing_key.issuer.as_ref(),
+            &signing_key.key_id.to_le_bytes()
+        ],
+        bump = signing_key.bump
+    )]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> AddIssuerKey<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_issuer_key(
+        &mut self,
+        issuer: Pubkey,
+        key_id: u16,
+        key_type: KeyType,
+        public_key: [u8; 65],
+        valid_from: i64,
+        valid_until: i64,
+        bump: u8,
+    ) -> Result<()> {
+        let config = &self.config;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            config.issuers.iter().any(|i| i.pubkey == issuer),
+            AttestationRegistryError::IssuerNotFound
+        );
+        require!(
+            IssuerSigningKey::is_well_formed(key_type, &public_key),
+            AttestationRegistryError::InvalidPublicKey
+        );
+        require!(
+            valid_until == 0 || valid_until > valid_from,
+            AttestationRegistryError::InvalidKeyWindow
+        );
+
+        self.signing_key.set_inner(IssuerSigningKey {
+            issuer,
+            key_id,
+            key_type,
+            public_key,
+            valid_from,
+            valid_until,
+            retired: false,
+            retired_at: 0,
+            bump,
+        });
+
+        emit!(IssuerKeyAdded {
+            issuer,
+            key_id,
+            key_type,
+            valid_from,
+            valid_until,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RotateIssuerKey<'info> {
+    /// Activates a new key now and lets the old one keep signing for `grace_secs`
+    pub fn rotate_issuer_key(
+        &mut self,
+        new_key_id: u16,
+        key_type: KeyType,
+        public_key: [u8; 65],
+        grace_secs: i64,
+        bump: u8,
+    ) -> Result<()> {
+        let old_key = &mut self.old_key;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !old_key.retired,
+            AttestationRegistryError::SigningKeyInactive
+        );
+        require!(
+            IssuerSigningKey::is_well_formed(key_type, &public_key),
+            AttestationRegistryError::InvalidPublicKey
+        );
+        require!(grace_secs >= 0, AttestationRegistryError::InvalidKeyWindow);
+
+        let old_valid_until = now
+            .checked_add(grace_secs)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+        if old_key.valid_until == 0 || old_key.valid_until > old_valid_until {
+            old_key.valid_until = old_valid_until;
+        }
+
+        self.new_key.set_inner(IssuerSigningKey {
+            issuer: old_key.issuer,
+            key_id: new_key_id,
+            key_type,
+            public_key,
+            valid_from: now,
+            valid_until: 0,
+            retired: false,
+            retired_at: 0,
+            bump,
+        });
+
+        emit!(IssuerKeyRotated {
+            issuer: old_key.issuer,
+            old_key_id: old_key.key_id,
+            new_key_id,
+            old_valid_until: old_key.valid_until,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RetireIssuerKey<'info> {
+    /// Stops the key from signing and revokes everything it already signed.
+    /// Use `rotate_issuer_key` instead when the key is being replaced in the
+    /// ordinary course and its attestations should stay valid.
+    pub fn retire_issuer_key(&mut self) -> Result<()> {
+        let signing_key = &mut self.signing_key;
+
+        require!(
+            !signing_key.retired,
+            AttestationRegistryError::SigningKeyInactive
+        );
+
+        signing_key.retired = true;
+        signing_key.retired_at = Clock::get()?.unix_timestamp;
+
+        emit!(IssuerKeyRetired {
+            issuer: signing_key.issuer,
+            key_id: signing_key.key_id,
+        });
+
+        Ok(())
+    }
+}
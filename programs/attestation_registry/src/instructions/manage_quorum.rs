@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::SchemaQuorumSet,
+    state::{
+        Attestation, AttestationStatus, Config, IssuerType, QuorumMode, QuorumResult,
+        SchemaDefinition, SchemaQuorum,
+    },
+    ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct SetSchemaQuorum<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + SchemaQuorum::INIT_SPACE,
+        seeds = [b"quorum".as_ref(), &schema_id.to_le_bytes()],
+        bump
+    )]
+    pub quorum: Account<'info, SchemaQuorum>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct CheckQuorum<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"quorum".as_ref(), &schema_id.to_le_bytes()],
+        bump = quorum.bump
+    )]
+    pub quorum: Account<'info, SchemaQuorum>,
+
+    /// CHECK: Subject identity
+    pub subject: UncheckedAccount<'info>,
+    // remaining_accounts: candidate `Attestation` accounts for (subject, schema_id)
+}
+
+impl<'info> SetSchemaQuorum<'info> {
+    pub fn set_schema_quorum(
+        &mut self,
+        schema_id: u16,
+        mode: QuorumMode,
+        threshold: u8,
+        issuers: Vec<Pubkey>,
+        bump: u8,
+    ) -> Result<()> {
+        require!(!self.config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(threshold > 0, AttestationRegistryError::InvalidQuorum);
+
+        match mode {
+            QuorumMode::NamedIssuers => {
+                require!(
+                    issuers.len() <= SchemaQuorum::MAX_ISSUERS
+                        && threshold as usize <= issuers.len(),
+                    AttestationRegistryError::InvalidQuorum
+                );
+                for (i, issuer) in issuers.iter().enumerate() {
+                    require!(
+                        !issuers[..i].contains(issuer),
+                        AttestationRegistryError::InvalidQuorum
+                    );
+                }
+            }
+            QuorumMode::DistinctIssuerTypes => {
+                require!(
+                    issuers.is_empty() && threshold as usize <= SchemaQuorum::MAX_DISTINCT_SOURCES,
+                    AttestationRegistryError::InvalidQuorum
+                );
+            }
+        }
+
+        self.quorum.set_inner(SchemaQuorum {
+            schema_id,
+            mode,
+            threshold,
+            issuers: issuers.clone(),
+            bump,
+        });
+
+        emit!(SchemaQuorumSet {
+            schema_id,
+            mode,
+            threshold,
+            issuers,
+        });
+
+        Ok(())
+    }
+}
+
+/*
+    For high-risk schemas a single issuer isn't enough. The caller passes every
+    attestation it wants counted as remaining accounts, each followed by its signing
+    key; each must belong to this program, match the subject and schema, and be
+    currently valid. Duplicate
+    issuers (or issuer types) only count once.
+*/
+impl<'info> CheckQuorum<'info> {
+    pub fn check_quorum(
+        &self,
+        schema_id: u16,
+        candidates: &'info [AccountInfo<'info>],
+    ) -> Result<QuorumResult> {
+        let config = &self.config;
+        let quorum = &self.quorum;
+        let subject = self.subject.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let mut counted_issuers: Vec<Pubkey> = Vec::new();
+        let mut counted_types: Vec<Option<IssuerType>> = Vec::new();
+
+        require!(
+            candidates.len().is_multiple_of(2),
+            AttestationRegistryError::InvalidAttestation
+        );
+
+        for pair in candidates.chunks_exact(2) {
+            let attestation = Account::<Attestation>::try_from(&pair[0])
+                .map_err(|_| AttestationRegistryError::InvalidAttestation)?;
+
+            require!(
+                attestation.subject == subject && attestation.schema_id == schema_id,
+                AttestationRegistryError::InvalidAttestation
+            );
+
+            let key_retired = attestation.signing_key_retired_at(&pair[1])?.is_some();
+            if attestation.status(config, key_retired, now) != AttestationStatus::Valid
+                || counted_issuers.contains(&attestation.issuer)
+            {
+                continue;
+            }
+
+            match quorum.mode {
+                QuorumMode::NamedIssuers => {
+                    if quorum.issuers.contains(&attestation.issuer) {
+                        counted_issuers.push(attestation.issuer);
+                    }
+                }
+                QuorumMode::DistinctIssuerTypes => {
+                    // Proofs count as one source of their own, see `MAX_DISTINCT_SOURCES`
+                    let issuer_type = if attestation.zk_verified {
+                        None
+                    } else {
+                        Some(config.enabled_issuer(&attestation.issuer)?.issuer_type)
+                    };
+                    if !counted_types.contains(&issuer_type) {
+                        counted_types.push(issuer_type);
+                        counted_issuers.push(attestation.issuer);
+                    }
+                }
+            }
+        }
+
+        let matched = counted_issuers.len() as u8;
+
+        Ok(QuorumResult {
+            qualified: matched >= quorum.threshold,
+            matched,
+            threshold: quorum.threshold,
+        })
+    }
+}
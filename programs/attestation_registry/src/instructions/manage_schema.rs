@@ -2,11 +2,39 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::AttestationRegistryError,
-    event::SchemaAdded,
-    state::{Config, SchemaType},
+    event::{SchemaAdded, SchemaDeprecated, SchemaExpiryBoundsSet, SchemaRemoved},
+    state::{Config, SchemaDefinition},
+    IssuerType, ANCHOR_DISCRIMINATOR, MAX_ATTESTATION_DATA_LEN,
 };
 
 #[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct AddSchema<'info> {
+    #[account(
+        mut,
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + SchemaDefinition::INIT_SPACE,
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
 pub struct ManageSchema<'info> {
     #[account(
         mut,
@@ -16,26 +44,170 @@ pub struct ManageSchema<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump,
+        constraint = schema.schema_id == schema_id @ AttestationRegistryError::SchemaNotFound
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(mut)]
     pub admin: Signer<'info>,
 }
 
-impl<'info> ManageSchema<'info> {
-    pub fn add_schema(&mut self, schema: SchemaType) -> Result<()> {
+impl<'info> AddSchema<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_schema(
+        &mut self,
+        schema_id: u16,
+        name: String,
+        version: u16,
+        field_layout_hash: [u8; 32],
+        default_expiry_secs: i64,
+        revocable: bool,
+        requires_subject_consent: bool,
+        max_data_len: u16,
+        required_issuer_type: Option<IssuerType>,
+        bump: u8,
+    ) -> Result<()> {
         let config = &mut self.config;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
         require!(
-            !config.schemas.contains(&schema),
+            !config.schemas.contains(&schema_id),
             AttestationRegistryError::SchemaAlreadyExists
         );
         require!(
             config.schemas.len() < Config::MAX_SCHEMAS,
             AttestationRegistryError::TooManySchemas
         );
+        require!(
+            !name.is_empty() && name.len() <= SchemaDefinition::MAX_NAME_LEN,
+            AttestationRegistryError::InvalidSchemaName
+        );
+        require!(
+            max_data_len as usize <= MAX_ATTESTATION_DATA_LEN,
+            AttestationRegistryError::DataTooLarge
+        );
+        // Bounds start as wide as the registry allows; narrow them with `set_schema_expiry_bounds`
+        SchemaDefinition::validate_expiry_bounds(
+            0,
+            default_expiry_secs,
+            config.max_expiry_secs,
+            config.max_expiry_secs,
+        )?;
+
+        config.schemas.push(schema_id);
+
+        self.schema.set_inner(SchemaDefinition {
+            schema_id,
+            name: name.clone(),
+            version,
+            field_layout_hash,
+            default_expiry_secs,
+            min_expiry_secs: 0,
+            max_expiry_secs: config.max_expiry_secs,
+            revocable,
+            requires_subject_consent,
+            max_data_len,
+            required_issuer_type,
+            deprecated: false,
+            created_at: Clock::get()?.unix_timestamp,
+            bump,
+            attestation_count: 0,
+        });
+
+        emit!(SchemaAdded {
+            schema_id,
+            name,
+            version,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ManageSchema<'info> {
+    /// Deprecated schemas stay readable but no new attestations can be posted against them
+    pub fn deprecate_schema(&mut self) -> Result<()> {
+        let schema = &mut self.schema;
+
+        require!(
+            !schema.deprecated,
+            AttestationRegistryError::SchemaDeprecated
+        );
+
+        schema.deprecated = true;
+
+        emit!(SchemaDeprecated {
+            schema_id: schema.schema_id,
+        });
+
+        Ok(())
+    }
+
+    /// Sets how short or long attestations under this schema may live. Existing
+    /// attestations keep their expiry; the bounds apply to new posts and extensions.
+    pub fn set_schema_expiry_bounds(
+        &mut self,
+        min_expiry_secs: i64,
+        default_expiry_secs: i64,
+        max_expiry_secs: i64,
+    ) -> Result<()> {
+        require!(
+            !self.config.paused.admin_ops,
+            AttestationRegistryError::Paused
+        );
+
+        SchemaDefinition::validate_expiry_bounds(
+            min_expiry_secs,
+            default_expiry_secs,
+            max_expiry_secs,
+            self.config.max_expiry_secs,
+        )?;
+
+        let schema = &mut self.schema;
+        schema.min_expiry_secs = min_expiry_secs;
+        schema.default_expiry_secs = default_expiry_secs;
+        schema.max_expiry_secs = max_expiry_secs;
+
+        emit!(SchemaExpiryBoundsSet {
+            schema_id: schema.schema_id,
+            min_expiry_secs,
+            default_expiry_secs,
+            max_expiry_secs,
+        });
+
+        Ok(())
+    }
+
+    /// Unregisters the schema, strips it from every issuer and closes the definition account.
+    /// Every attestation under it has to be closed first, or it would outlive its schema.
+    pub fn remove_schema(&mut self) -> Result<()> {
+        let config = &mut self.config;
+        let schema_id = self.schema.schema_id;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            self.schema.attestation_count == 0,
+            AttestationRegistryError::SchemaInUse
+        );
+
+        let pos = config
+            .schemas
+            .iter()
+            .position(|s| *s == schema_id)
+            .ok_or(AttestationRegistryError::SchemaNotFound)?;
+
+        config.schemas.remove(pos);
+        for issuer in config.issuers.iter_mut() {
+            issuer.allowed_schemas.retain(|s| *s != schema_id);
+        }
 
-        config.schemas.push(schema);
+        self.schema.close(self.admin.to_account_info())?;
 
-        emit!(SchemaAdded { schema });
+        emit!(SchemaRemoved { schema_id });
 
         Ok(())
     }
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::SchemaVerifyingKeySet,
+    state::{Config, SchemaDefinition, SchemaVerifyingKey},
+    ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct SetVerifyingKey<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + SchemaVerifyingKey::INIT_SPACE,
+        seeds = [b"verifying_key".as_ref(), &schema_id.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: Box<Account<'info, SchemaVerifyingKey>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct ExtendVerifyingKey<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"verifying_key".as_ref(), &schema_id.to_le_bytes()],
+        bump = verifying_key.bump
+    )]
+    pub verifying_key: Box<Account<'info, SchemaVerifyingKey>>,
+
+    pub admin: Signer<'info>,
+}
+
+/*
+    A full key with 8 public inputs doesn't fit in one transaction, so
+    `set_verifying_key` writes the fixed points and the first IC points, and
+    `extend_verifying_key` appends the rest. Proofs are refused until every
+    declared input has its point. Keys are write-once; a new circuit gets a
+    new schema.
+*/
+impl<'info> SetVerifyingKey<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_verifying_key(
+        &mut self,
+        schema_id: u16,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        public_inputs: u8,
+        ic: Vec<[u8; 64]>,
+        subject_input_index: u8,
+        timestamp_input_index: u8,
+        bump: u8,
+    ) -> Result<()> {
+        require!(!self.config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            (public_inputs as usize) <= SchemaVerifyingKey::MAX_PUBLIC_INPUTS
+                && ic.len() <= public_inputs as usize + 1,
+            AttestationRegistryError::InvalidVerifyingKey
+        );
+        require!(
+            subject_input_index < public_inputs
+                && timestamp_input_index < public_inputs
+                && subject_input_index != timestamp_input_index,
+            AttestationRegistryError::InvalidVerifyingKey
+        );
+
+        self.verifying_key.set_inner(SchemaVerifyingKey {
+            schema_id,
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            public_inputs,
+            ic,
+            subject_input_index,
+            timestamp_input_index,
+            bump,
+        });
+
+        emit!(SchemaVerifyingKeySet {
+            schema_id,
+            public_inputs: self.verifying_key.public_input_count() as u8,
+            complete: self.verifying_key.is_complete(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ExtendVerifyingKey<'info> {
+    pub fn extend_verifying_key(&mut self, schema_id: u16, ic: Vec<[u8; 64]>) -> Result<()> {
+        require!(!self.config.paused.admin_ops, AttestationRegistryError::Paused);
+
+        let verifying_key = &mut self.verifying_key;
+        require!(
+            verifying_key.ic.len() + ic.len() <= verifying_key.public_inputs as usize + 1,
+            AttestationRegistryError::InvalidVerifyingKey
+        );
+
+        verifying_key.ic.extend(ic);
+
+        emit!(SchemaVerifyingKeySet {
+            schema_id,
+            public_inputs: verifying_key.public_input_count() as u8,
+            complete: verifying_key.is_complete(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::{AttestationMaterialized, AttestationPosted},
+    instructions::verify_in_batch::check_batch_leaf,
+    state::{
+        Attestation, AttestationBatch, AttestationTombstone, Config, IssuerSigningKey,
+        SchemaDefinition,
+    },
+    ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct MaterializeAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(
+        seeds = [b"batch", batch.issuer.as_ref(), &batch.batch_id.to_le_bytes()],
+        bump = batch.bump
+    )]
+    pub batch: Account<'info, AttestationBatch>,
+
+    #[account(address = batch.signing_key @ AttestationRegistryError::SigningKeyMismatch)]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Subject identity, bound through the Merkle leaf; must sign when the
+    /// schema requires subject consent
+    pub subject: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Attestation::space(0),
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &schema_id.to_le_bytes(),
+            batch.issuer.as_ref()
+        ],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + AttestationTombstone::INIT_SPACE,
+        seeds = [b"attest_tombstone", attestation.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, AttestationTombstone>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+    Batched leaves are only commitments; a consumer that wants a regular
+    `Attestation` account (e.g. to pass it to another program) can have one
+    created on demand by proving the leaf is in the batch. Anyone may pay for it.
+    A leaf only ever yields version 1, so it can't be materialized again once
+    something has been closed at its address.
+*/
+impl<'info> MaterializeAttestation<'info> {
+    pub fn materialize_attestation(
+        &mut self,
+        schema_id: u16,
+        claim_hash: [u8; 32],
+        expiry_ts: i64,
+        proof: Vec<[u8; 32]>,
+        bump: u8,
+        tombstone_bump: u8,
+    ) -> Result<()> {
+        require!(!self.config.paused.posting, AttestationRegistryError::Paused);
+        require!(
+            self.tombstone.last_version == 0,
+            AttestationRegistryError::LeafAlreadyMaterialized
+        );
+        if self.schema.requires_subject_consent {
+            require!(
+                self.subject.is_signer,
+                AttestationRegistryError::SubjectConsentRequired
+            );
+        }
+
+        check_batch_leaf(
+            &self.config,
+            &self.schema,
+            &self.batch,
+            &self.signing_key,
+            &self.subject.key(),
+            schema_id,
+            claim_hash,
+            expiry_ts,
+            &proof,
+        )?;
+
+        self.schema.track_opened()?;
+
+        let batch = &self.batch;
+        self.attestation.set_inner(Attestation {
+            subject: self.subject.key(),
+            schema_id,
+            claim_hash,
+            issuer: batch.issuer,
+            issued_at: batch.posted_at,
+            expiry_ts,
+            revoked: false,
+            bump,
+            revoked_at: 0,
+            revocation_reason: None,
+            issuer_epoch: batch.issuer_epoch,
+            payer: self.payer.key(),
+            signing_key: batch.signing_key,
+            zk_verified: false,
+            version: 1,
+            history_enabled: false,
+            data: Vec::new(),
+        });
+        self.tombstone.set_inner(AttestationTombstone {
+            attestation: self.attestation.key(),
+            last_version: 1,
+            bump: tombstone_bump,
+        });
+
+        emit!(AttestationPosted {
+            subject: self.subject.key(),
+            schema_id,
+            issuer: batch.issuer,
+            claim_hash,
+            expiry_ts,
+            version: 1,
+            data: Vec::new(),
+        });
+
+        emit!(AttestationMaterialized {
+            subject: self.subject.key(),
+            schema_id,
+            issuer: batch.issuer,
+            batch_id: batch.batch_id,
+        });
+
+        Ok(())
+    }
+}
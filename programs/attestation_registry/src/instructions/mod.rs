@@ -1,19 +1,49 @@
 pub mod initialize_config;
 pub mod manage_issuer;
+pub mod manage_issuer_key;
+pub mod manage_issuer_application;
 pub mod post_attestation;
+pub mod post_attestation_batch;
+pub mod post_zk_attestation;
+pub mod verify_in_batch;
+pub mod materialize_attestation;
+pub mod enable_attestation_history;
 pub mod manage_schema;
+pub mod manage_quorum;
+pub mod manage_verifying_key;
 pub mod revoke_attestation;
+pub mod subject_attestation;
 pub mod set_max_expiry;
+pub mod set_revocation_retention;
+pub mod set_issuer_bond;
+pub mod close_expired_attestation;
 pub mod set_paused;
 pub mod set_admin;
+pub mod set_guardian;
 pub mod update_expiry;
+pub mod verify_attestation;
 
 pub use initialize_config::*;
 pub use manage_issuer::*;
+pub use manage_issuer_key::*;
+pub use manage_issuer_application::*;
 pub use post_attestation::*;
+pub use post_attestation_batch::*;
+pub use post_zk_attestation::*;
+pub use verify_in_batch::*;
+pub use materialize_attestation::*;
+pub use enable_attestation_history::*;
 pub use manage_schema::*;
+pub use manage_quorum::*;
+pub use manage_verifying_key::*;
 pub use revoke_attestation::*;
+pub use subject_attestation::*;
 pub use set_max_expiry::*;
+pub use set_revocation_retention::*;
+pub use set_issuer_bond::*;
+pub use close_expired_attestation::*;
 pub use set_paused::*;
 pub use set_admin::*;
+pub use set_guardian::*;
 pub use update_expiry::*;
+pub use verify_attestation::*;
@@ -1,17 +1,27 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::{secp256k1_recover::secp256k1_recover},
+    solana_program::{
+        ed25519_program, keccak,
+        secp256k1_recover::secp256k1_recover,
+        sysvar::instructions::{
+            self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+        },
+    },
+    system_program::{transfer, Transfer},
 };
 
 use crate::{
     error::AttestationRegistryError,
     event::AttestationPosted,
-    state::{Attestation, Config, SchemaType},
-    IssuerType,
+    state::{
+        Attestation, AttestationHistory, AttestationTombstone, Config, IssuerSigningKey, KeyType,
+        SchemaDefinition,
+    },
+    ANCHOR_DISCRIMINATOR,
 };
 
 #[derive(Accounts)]
-#[instruction(schema_id: SchemaType)]
+#[instruction(schema_id: u16, claim_hash: [u8; 32], data: Vec<u8>)]
 pub struct PostAttestation<'info> {
     #[account(
         seeds = [b"attest_config"],
@@ -19,107 +29,326 @@ pub struct PostAttestation<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
     /// CHECK: Ethereum address mapped to a 32-byte Pubkey; does NOT sign
     pub issuer: UncheckedAccount<'info>,
 
-    /// CHECK: subject may or may not be a Signer depending on your policy
+    #[account(
+        seeds = [
+            b"issuer_key".as_ref(),
+            issuer.key().as_ref(),
+            &signing_key.key_id.to_le_bytes()
+        ],
+        bump = signing_key.bump
+    )]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    /// CHECK: must sign when the schema requires subject consent
     pub subject: UncheckedAccount<'info>,
 
     #[account(
-        init_if_needed,
+        init,
         payer = payer,
-        space = 8 + Attestation::INIT_SPACE,
+        space = Attestation::space(data.len()),
         seeds = [
             b"attest",
             subject.key().as_ref(),
-            &[schema_id as u8],
+            &schema_id.to_le_bytes(),
             issuer.key().as_ref()
         ],
         bump
     )]
     pub attestation: Account<'info, Attestation>,
 
+    // Already exists when an earlier attestation at this address was closed
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + AttestationTombstone::INIT_SPACE,
+        seeds = [b"attest_tombstone", attestation.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, AttestationTombstone>,
+
+    /// CHECK: instructions sysvar, read to find the Ed25519 verification instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct RepostAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ethereum address mapped to a 32-byte Pubkey; does NOT sign
+    pub issuer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"issuer_key".as_ref(),
+            issuer.key().as_ref(),
+            &signing_key.key_id.to_le_bytes()
+        ],
+        bump = signing_key.bump
+    )]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    /// CHECK: must sign when the schema requires subject consent
+    pub subject: UncheckedAccount<'info>,
+
+    // Resized by hand, see `resize_attestation`
+    #[account(
+        mut,
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &schema_id.to_le_bytes(),
+            issuer.key().as_ref()
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [b"attest_tombstone", attestation.key().as_ref()],
+        bump = tombstone.bump
+    )]
+    pub tombstone: Account<'info, AttestationTombstone>,
+
+    #[account(
+        mut,
+        seeds = [b"attest_history", attestation.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Option<Account<'info, AttestationHistory>>,
+
+    /// CHECK: instructions sysvar, read to find the Ed25519 verification instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> PostAttestation<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn post_attestation(
         &mut self,
-        schema_id: SchemaType,
+        schema_id: u16,
         claim_hash: [u8; 32],
+        data: Vec<u8>,
         expiry_ts: i64,
         signature_bytes: [u8; 64],
         recover_id: u8,
-        allocator_from_proof: [u8; 65],
         bump: u8,
+        tombstone_bump: u8,
     ) -> Result<()> {
-        let config = &self.config;
-        let attestation = &mut self.attestation;
-        let clock = Clock::get()?;
+        // Carries on from whatever was closed at this address before
+        let version = self.tombstone.next_version()?;
+        let (expiry_ts, issuer_epoch) = check_post(
+            &self.config,
+            &self.schema,
+            &self.issuer.key(),
+            &self.subject,
+            &self.signing_key,
+            &self.instructions_sysvar,
+            schema_id,
+            &claim_hash,
+            &data,
+            expiry_ts,
+            &signature_bytes,
+            recover_id,
+            version,
+        )?;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
+        self.schema.track_opened()?;
 
-        let issuer_entry = config
-            .issuers
-            .iter()
-            .find(|i| i.pubkey == self.issuer.key())
-            .ok_or(AttestationRegistryError::IssuerNotFound)?;
+        self.attestation.set_inner(Attestation {
+            subject: self.subject.key(),
+            schema_id,
+            claim_hash,
+            issuer: self.issuer.key(),
+            issued_at: Clock::get()?.unix_timestamp,
+            expiry_ts,
+            revoked: false,
+            bump,
+            revoked_at: 0,
+            revocation_reason: None,
+            issuer_epoch,
+            payer: self.payer.key(),
+            signing_key: self.signing_key.key(),
+            zk_verified: false,
+            version,
+            history_enabled: false,
+            data,
+        });
+        self.tombstone.set_inner(AttestationTombstone {
+            attestation: self.attestation.key(),
+            last_version: version,
+            bump: tombstone_bump,
+        });
+        emit_posted(&self.attestation);
 
-        require!(
-            issuer_entry.enabled,
-            AttestationRegistryError::IssuerDisabled
-        );
+        Ok(())
+    }
+
+    /// Re-posting can change the payload size, so grow or shrink the account to fit.
+    /// Extra rent on growth is paid by `payer`; surplus lamports on shrink stay in the
+    /// account until it is closed. Done by hand because `init_if_needed` rejects an
+    /// existing account whose size differs from `space` before the handler runs.
+    pub(crate) fn resize_attestation(
+        attestation: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        data_len: usize,
+    ) -> Result<()> {
+        let new_space = Attestation::space(data_len);
+
+        if attestation.data_len() == new_space {
+            return Ok(());
+        }
+
+        let required = Rent::get()?.minimum_balance(new_space);
+        let current = attestation.lamports();
+        if required > current {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: attestation.clone(),
+                    },
+                ),
+                required - current,
+            )?;
+        }
+
+        attestation.realloc(new_space, false)?;
 
+        Ok(())
+    }
+
+    /// What the issuer signs for a post: the program, the attestation's seeds, its
+    /// contents and the version it will become. `expiry_ts` is the value passed to the
+    /// instruction, so zero still signs for the schema default.
+    pub fn message_hash(
+        subject: &Pubkey,
+        schema_id: u16,
+        issuer: &Pubkey,
+        claim_hash: &[u8; 32],
+        expiry_ts: i64,
+        data: &[u8],
+        version: u32,
+    ) -> [u8; 32] {
+        keccak::hashv(&[
+            crate::ID.as_ref(),
+            subject.as_ref(),
+            &schema_id.to_le_bytes(),
+            issuer.as_ref(),
+            claim_hash,
+            &expiry_ts.to_le_bytes(),
+            &keccak::hash(data).to_bytes(),
+            &version.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub(crate) fn verify_key_sig(
+        signing_key: &IssuerSigningKey,
+        instructions_sysvar: &AccountInfo,
+        signature_bytes: &[u8; 64],
+        recover_id: u8,
+        message_hash: &[u8; 32],
+    ) -> Result<()> {
         require!(
-            config.schemas.contains(&schema_id),
-            AttestationRegistryError::SchemaNotAllowed
+            signing_key.is_active(Clock::get()?.unix_timestamp),
+            AttestationRegistryError::SigningKeyInactive
         );
 
+        match signing_key.key_type {
+            KeyType::Secp256k1 => Self::verify_eth_sig(
+                &signing_key.public_key,
+                signature_bytes,
+                recover_id,
+                message_hash,
+            ),
+            KeyType::Ed25519 => Self::verify_ed25519_ix(
+                instructions_sysvar,
+                &signing_key.public_key[..32],
+                signature_bytes,
+                message_hash,
+            ),
+        }
+    }
+
+    /// Ed25519 can't be verified in-program, so the transaction must carry an Ed25519
+    /// native program instruction right before this one over the same key, signature
+    /// and message.
+    fn verify_ed25519_ix(
+        instructions_sysvar: &AccountInfo,
+        public_key: &[u8],
+        signature_bytes: &[u8; 64],
+        message_hash: &[u8; 32],
+    ) -> Result<()> {
+        let current = load_current_index_checked(instructions_sysvar)? as usize;
+        require!(current > 0, AttestationRegistryError::InvalidSignature);
+
+        let ix = load_instruction_at_checked(current - 1, instructions_sysvar)?;
         require!(
-            expiry_ts > clock.unix_timestamp,
-            AttestationRegistryError::InvalidExpiry
+            ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+            AttestationRegistryError::InvalidSignature
         );
+
+        // [num_signatures: u8, padding: u8, 7 x u16 offsets, ...payload]
+        let data = &ix.data;
         require!(
-            expiry_ts <= clock.unix_timestamp + config.max_expiry_secs,
-            AttestationRegistryError::ExpiryTooFar
+            data.len() >= 16 && data[0] == 1,
+            AttestationRegistryError::InvalidSignature
         );
 
-        match issuer_entry.issuer_type {
-            IssuerType::Ethereum => {
-                Self::verify_eth_sig(
-                    &allocator_from_proof,
-                    &signature_bytes,
-                    recover_id,
-                    &claim_hash,
-                )?;
-            }
-            IssuerType::Solana => {
-                require!(
-                    signature_bytes.len() == 64,
-                    AttestationRegistryError::InvalidSignature
-                );
-            }
-        }
-
-        attestation.subject = self.subject.key();
-        attestation.schema_id = schema_id;
-        attestation.claim_hash = claim_hash;
-        attestation.issuer = self.issuer.key();
-        attestation.issued_at = clock.unix_timestamp;
-        attestation.expiry_ts = expiry_ts;
-        attestation.revoked = false;
-        attestation.bump = bump;
+        let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+        let field = |offset: usize, len: usize| {
+            data.get(offset..offset + len)
+                .ok_or(AttestationRegistryError::InvalidSignature)
+        };
 
-        emit!(AttestationPosted {
-            subject: attestation.subject,
-            schema_id,
-            issuer: attestation.issuer,
-            claim_hash,
-            expiry_ts,
-        });
+        // Every part must live in the Ed25519 instruction itself (index u16::MAX)
+        let self_ix = u16::MAX as usize;
+        require!(
+            read_u16(4) == self_ix && read_u16(8) == self_ix && read_u16(14) == self_ix,
+            AttestationRegistryError::InvalidSignature
+        );
+        require!(
+            field(read_u16(2), 64)? == signature_bytes.as_slice()
+                && field(read_u16(6), 32)? == public_key
+                && read_u16(12) == message_hash.len()
+                && field(read_u16(10), 32)? == message_hash.as_slice(),
+            AttestationRegistryError::InvalidSignature
+        );
 
         Ok(())
     }
@@ -144,3 +373,158 @@ impl<'info> PostAttestation<'info> {
         Ok(())
     } 
 }
+
+impl<'info> RepostAttestation<'info> {
+    /// Replaces an existing attestation with a newly signed version, archiving the
+    /// old one first when history is enabled
+    #[allow(clippy::too_many_arguments)]
+    pub fn repost_attestation(
+        &mut self,
+        schema_id: u16,
+        claim_hash: [u8; 32],
+        data: Vec<u8>,
+        expiry_ts: i64,
+        signature_bytes: [u8; 64],
+        recover_id: u8,
+    ) -> Result<()> {
+        // The signature only covers the version it creates, so once an attestation is
+        // revoked or re-posted an earlier signature can't be replayed to restore it
+        let version = self.tombstone.next_version()?;
+        let (expiry_ts, issuer_epoch) = check_post(
+            &self.config,
+            &self.schema,
+            &self.issuer.key(),
+            &self.subject,
+            &self.signing_key,
+            &self.instructions_sysvar,
+            schema_id,
+            &claim_hash,
+            &data,
+            expiry_ts,
+            &signature_bytes,
+            recover_id,
+            version,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.attestation.archive(self.history.as_mut(), now)?;
+
+        PostAttestation::resize_attestation(
+            &self.attestation.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            data.len(),
+        )?;
+
+        let attestation = &mut self.attestation;
+        attestation.claim_hash = claim_hash;
+        attestation.issued_at = now;
+        attestation.expiry_ts = expiry_ts;
+        attestation.revoked = false;
+        attestation.revoked_at = 0;
+        attestation.revocation_reason = None;
+        attestation.issuer_epoch = issuer_epoch;
+        attestation.signing_key = self.signing_key.key();
+        attestation.version = version;
+        attestation.data = data;
+        self.tombstone.last_version = version;
+        emit_posted(attestation);
+
+        Ok(())
+    }
+}
+
+/// Checks shared by first posts and re-posts: issuer, schema, consent, payload size,
+/// expiry and the signature over `version`. Returns the expiry to store and the
+/// issuer's current revocation epoch.
+#[allow(clippy::too_many_arguments)]
+fn check_post(
+    config: &Config,
+    schema: &SchemaDefinition,
+    issuer: &Pubkey,
+    subject: &AccountInfo,
+    signing_key: &IssuerSigningKey,
+    instructions_sysvar: &AccountInfo,
+    schema_id: u16,
+    claim_hash: &[u8; 32],
+    data: &[u8],
+    expiry_ts: i64,
+    signature_bytes: &[u8; 64],
+    recover_id: u8,
+    version: u32,
+) -> Result<(i64, u32)> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!config.paused.posting, AttestationRegistryError::Paused);
+
+    let issuer_entry = config.enabled_issuer(issuer)?;
+
+    require!(
+        !schema.deprecated,
+        AttestationRegistryError::SchemaDeprecated
+    );
+    if let Some(required_issuer_type) = schema.required_issuer_type {
+        require!(
+            issuer_entry.issuer_type == required_issuer_type,
+            AttestationRegistryError::UnsupportedIssuerType
+        );
+    }
+
+    require!(
+        issuer_entry.is_schema_allowed(schema_id),
+        AttestationRegistryError::IssuerSchemaNotAuthorized
+    );
+
+    if schema.requires_subject_consent {
+        require!(
+            subject.is_signer,
+            AttestationRegistryError::SubjectConsentRequired
+        );
+    }
+
+    require!(
+        data.len() <= schema.max_data_len as usize,
+        AttestationRegistryError::DataTooLarge
+    );
+
+    // Zero means "use the schema's default validity period"
+    let stored_expiry_ts = if expiry_ts == 0 {
+        now + schema.default_expiry_secs
+    } else {
+        expiry_ts
+    };
+
+    schema.check_expiry(now, stored_expiry_ts)?;
+
+    let signed_message = PostAttestation::message_hash(
+        subject.key,
+        schema_id,
+        issuer,
+        claim_hash,
+        expiry_ts,
+        data,
+        version,
+    );
+
+    PostAttestation::verify_key_sig(
+        signing_key,
+        instructions_sysvar,
+        signature_bytes,
+        recover_id,
+        &signed_message,
+    )?;
+
+    Ok((stored_expiry_ts, issuer_entry.revocation_epoch))
+}
+
+fn emit_posted(attestation: &Attestation) {
+    emit!(AttestationPosted {
+        subject: attestation.subject,
+        schema_id: attestation.schema_id,
+        issuer: attestation.issuer,
+        claim_hash: attestation.claim_hash,
+        expiry_ts: attestation.expiry_ts,
+        version: attestation.version,
+        data: attestation.data.clone(),
+    });
+}
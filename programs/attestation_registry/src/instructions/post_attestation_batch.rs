@@ -0,0 +1,111 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{keccak, sysvar::instructions as instructions_sysvar},
+};
+
+use crate::{
+    error::AttestationRegistryError,
+    event::AttestationBatchPosted,
+    state::{AttestationBatch, Config, IssuerSigningKey},
+    PostAttestation, ANCHOR_DISCRIMINATOR,
+};
+
+#[derive(Accounts)]
+#[instruction(batch_id: u64)]
+pub struct PostAttestationBatch<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: issuer identity; authorised by the signature over the batch root
+    pub issuer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"issuer_key".as_ref(),
+            issuer.key().as_ref(),
+            &signing_key.key_id.to_le_bytes()
+        ],
+        bump = signing_key.bump
+    )]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + AttestationBatch::INIT_SPACE,
+        seeds = [b"batch", issuer.key().as_ref(), &batch_id.to_le_bytes()],
+        bump
+    )]
+    pub batch: Account<'info, AttestationBatch>,
+
+    /// CHECK: instructions sysvar, read to find the Ed25519 verification instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostAttestationBatch<'info> {
+    pub fn post_attestation_batch(
+        &mut self,
+        batch_id: u64,
+        merkle_root: [u8; 32],
+        leaf_count: u32,
+        signature_bytes: [u8; 64],
+        recover_id: u8,
+        bump: u8,
+    ) -> Result<()> {
+        let config = &self.config;
+
+        require!(!config.paused.posting, AttestationRegistryError::Paused);
+        require!(
+            leaf_count > 0 && merkle_root != [0u8; 32],
+            AttestationRegistryError::InvalidBatch
+        );
+
+        let issuer_entry = config.enabled_issuer(&self.issuer.key())?;
+
+        // Bind the program, issuer and batch id so a signed root can't be replayed
+        // under another deployment, issuer or id
+        let signed_message = keccak::hashv(&[
+            crate::ID.as_ref(),
+            self.issuer.key().as_ref(),
+            &merkle_root,
+            &batch_id.to_le_bytes(),
+        ])
+        .to_bytes();
+        PostAttestation::verify_key_sig(
+            &self.signing_key,
+            &self.instructions_sysvar,
+            &signature_bytes,
+            recover_id,
+            &signed_message,
+        )?;
+
+        self.batch.set_inner(AttestationBatch {
+            issuer: self.issuer.key(),
+            batch_id,
+            merkle_root,
+            leaf_count,
+            posted_at: Clock::get()?.unix_timestamp,
+            bump,
+            issuer_epoch: issuer_entry.revocation_epoch,
+            signing_key: self.signing_key.key(),
+        });
+
+        emit!(AttestationBatchPosted {
+            issuer: self.issuer.key(),
+            batch_id,
+            merkle_root,
+            leaf_count,
+        });
+
+        Ok(())
+    }
+}
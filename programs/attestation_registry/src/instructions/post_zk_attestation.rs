@@ -0,0 +1,187 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+use crate::{
+    error::AttestationRegistryError,
+    event::AttestationPosted,
+    groth16::{verify_proof, Groth16Proof},
+    instructions::PostAttestation,
+    state::{Attestation, AttestationHistory, Config, SchemaDefinition, SchemaVerifyingKey},
+    MAX_PROOF_AGE_SECS,
+};
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16, public_inputs: Vec<[u8; 32]>)]
+pub struct PostZkAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(
+        seeds = [b"verifying_key".as_ref(), &schema_id.to_le_bytes()],
+        bump = verifying_key.bump
+    )]
+    pub verifying_key: Box<Account<'info, SchemaVerifyingKey>>,
+
+    // Posts for themselves; no issuer is involved
+    #[account(mut)]
+    pub subject: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = subject,
+        space = Attestation::space(public_inputs.len() * 32),
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &schema_id.to_le_bytes(),
+            verifying_key.key().as_ref()
+        ],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [b"attest_history", attestation.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Option<Account<'info, AttestationHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostZkAttestation<'info> {
+    /// Records an attestation backed by a Groth16 proof instead of an issuer signature.
+    /// The public inputs are stored as the payload and hashed into `claim_hash`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn post_zk_attestation(
+        &mut self,
+        schema_id: u16,
+        public_inputs: Vec<[u8; 32]>,
+        proof_a: [u8; 64],
+        proof_b: [u8; 128],
+        proof_c: [u8; 64],
+        expiry_ts: i64,
+        bump: u8,
+    ) -> Result<()> {
+        let config = &self.config;
+        let schema = &self.schema;
+        let verifying_key = &self.verifying_key;
+        let clock = Clock::get()?;
+
+        require!(!config.paused.posting, AttestationRegistryError::Paused);
+        require!(
+            !schema.deprecated,
+            AttestationRegistryError::SchemaDeprecated
+        );
+
+        let data = public_inputs.concat();
+        require!(
+            data.len() <= schema.max_data_len as usize,
+            AttestationRegistryError::DataTooLarge
+        );
+
+        require!(
+            verifying_key.is_complete(),
+            AttestationRegistryError::VerifyingKeyIncomplete
+        );
+
+        let signal = public_inputs
+            .get(verifying_key.subject_input_index as usize)
+            .ok_or(AttestationRegistryError::InvalidPublicInputs)?;
+        require!(
+            *signal == SchemaVerifyingKey::subject_signal(&self.subject.key()),
+            AttestationRegistryError::ProofSubjectMismatch
+        );
+
+        // A proof is only good for a short while, and only once: re-posting needs a
+        // proof generated after the current attestation was issued
+        let proof_ts = verifying_key.proof_timestamp(&public_inputs)?;
+        require!(
+            proof_ts <= clock.unix_timestamp
+                && clock.unix_timestamp - proof_ts <= MAX_PROOF_AGE_SECS
+                && (self.attestation.version == 0 || proof_ts > self.attestation.issued_at),
+            AttestationRegistryError::StaleProof
+        );
+
+        // Zero means "use the schema's default validity period"
+        let expiry_ts = if expiry_ts == 0 {
+            clock.unix_timestamp + schema.default_expiry_secs
+        } else {
+            expiry_ts
+        };
+
+        schema.check_expiry(clock.unix_timestamp, expiry_ts)?;
+
+        verify_proof(
+            verifying_key,
+            &Groth16Proof {
+                a: proof_a,
+                b: proof_b,
+                c: proof_c,
+            },
+            &public_inputs,
+        )?;
+
+        PostAttestation::resize_attestation(
+            &self.attestation.to_account_info(),
+            &self.subject.to_account_info(),
+            &self.system_program.to_account_info(),
+            data.len(),
+        )?;
+
+        let claim_hash = keccak::hash(&data).to_bytes();
+        let issuer = verifying_key.key();
+
+        self.attestation
+            .archive(self.history.as_mut(), clock.unix_timestamp)?;
+
+        if self.attestation.version == 0 {
+            self.schema.track_opened()?;
+        }
+
+        let attestation = &mut self.attestation;
+        if attestation.payer == Pubkey::default() {
+            attestation.payer = self.subject.key();
+        }
+        attestation.subject = self.subject.key();
+        attestation.schema_id = schema_id;
+        attestation.claim_hash = claim_hash;
+        attestation.issuer = issuer;
+        attestation.issued_at = clock.unix_timestamp;
+        attestation.expiry_ts = expiry_ts;
+        attestation.revoked = false;
+        attestation.bump = bump;
+        attestation.revoked_at = 0;
+        attestation.revocation_reason = None;
+        attestation.issuer_epoch = 0;
+        attestation.signing_key = Pubkey::default();
+        attestation.zk_verified = true;
+        attestation.version = attestation
+            .version
+            .checked_add(1)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+        attestation.data = data.clone();
+
+        emit!(AttestationPosted {
+            subject: attestation.subject,
+            schema_id,
+            issuer,
+            claim_hash,
+            expiry_ts,
+            version: attestation.version,
+            data,
+        });
+
+        Ok(())
+    }
+}
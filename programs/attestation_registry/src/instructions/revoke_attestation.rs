@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 use crate::{
     error::AttestationRegistryError,
     event::AttestationRevoked,
-    state::{Attestation, Config},
+    state::{Attestation, Config, RevocationReason, SchemaDefinition},
 };
 
 #[derive(Accounts)]
@@ -22,7 +22,7 @@ pub struct RevokeAttestation<'info> {
         seeds = [
             b"attest",
             subject.key().as_ref(),
-            &[attestation.schema_id as u8],
+            &attestation.schema_id.to_le_bytes(),
             attestation.issuer.as_ref()
         ],
         bump = attestation.bump,
@@ -30,19 +30,29 @@ pub struct RevokeAttestation<'info> {
     )]
     pub attestation: Account<'info, Attestation>,
 
+    #[account(
+        seeds = [b"schema".as_ref(), &attestation.schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
     pub signer: Signer<'info>,
 }
 
 impl<'info> RevokeAttestation<'info> {
-    pub fn revoke_attestation(&mut self) -> Result<()> {
+    pub fn revoke_attestation(&mut self, reason: RevocationReason) -> Result<()> {
         let config = &self.config;
         let attestation = &mut self.attestation;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
+        require!(!config.paused.revocation, AttestationRegistryError::Paused);
         require!(
             !attestation.revoked,
             AttestationRegistryError::AlreadyRevoked
         );
+        require!(
+            self.schema.revocable,
+            AttestationRegistryError::SchemaNotRevocable
+        );
 
         // Only issuer or admin can revoke
         let signer = self.signer.key();
@@ -51,12 +61,17 @@ impl<'info> RevokeAttestation<'info> {
             AttestationRegistryError::Unauthorized
         );
 
+        let now = Clock::get()?.unix_timestamp;
         attestation.revoked = true;
+        attestation.revoked_at = now;
+        attestation.revocation_reason = Some(reason);
 
         emit!(AttestationRevoked {
             subject: attestation.subject,
             schema_id: attestation.schema_id,
             issuer: attestation.issuer,
+            reason,
+            revoked_at: now,
         });
 
         Ok(())
@@ -19,7 +19,6 @@ impl<'info> SetAdmin<'info> {
     pub fn set_admin(&mut self, new_admin: Pubkey) -> Result<()> {
         let config = &mut self.config;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
         require!(
             new_admin != Pubkey::default(),
             AttestationRegistryError::InvalidAdmin
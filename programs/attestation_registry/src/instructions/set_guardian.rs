@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{event::GuardianChanged, state::Config};
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SetGuardian<'info> {
+    /// `Pubkey::default()` removes the guardian, leaving pausing to the admin
+    pub fn set_guardian(&mut self, new_guardian: Pubkey) -> Result<()> {
+        let config = &mut self.config;
+
+        let old_guardian = config.guardian;
+        config.guardian = new_guardian;
+
+        emit!(GuardianChanged {
+            old_guardian,
+            new_guardian,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AttestationRegistryError, event::IssuerBondChanged, state::Config};
+
+#[derive(Accounts)]
+pub struct SetIssuerBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SetIssuerBond<'info> {
+    pub fn set_issuer_bond(&mut self, bond_mint: Pubkey, min_issuer_bond: u64) -> Result<()> {
+        let config = &mut self.config;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            bond_mint != Pubkey::default(),
+            AttestationRegistryError::InvalidBondMint
+        );
+
+        config.bond_mint = bond_mint;
+        config.min_issuer_bond = min_issuer_bond;
+
+        emit!(IssuerBondChanged {
+            bond_mint,
+            min_issuer_bond
+        });
+
+        Ok(())
+    }
+}
@@ -16,10 +16,11 @@ pub struct SetMaxExpiry<'info> {
 }
 
 impl<'info> SetMaxExpiry<'info> {
+    /// Ceiling for per-schema expiry bounds; schemas already above it keep their bounds
     pub fn set_max_expiry(&mut self, max_expiry_secs: i64) -> Result<()> {
         let config = &mut self.config;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
         require!(max_expiry_secs > 0, AttestationRegistryError::InvalidExpiry);
 
         config.max_expiry_secs = max_expiry_secs;
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{state::Config, event::PauseChanged};
+use crate::{
+    error::AttestationRegistryError,
+    event::PauseChanged,
+    state::{Config, PauseFlags},
+};
 
 #[derive(Accounts)]
 pub struct SetPaused<'info> {
@@ -15,13 +19,46 @@ pub struct SetPaused<'info> {
     pub admin: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        constraint = authority.key() == config.guardian
+            || authority.key() == config.admin @ AttestationRegistryError::NotGuardian
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
 impl<'info> SetPaused<'info> {
-    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+    /// Replaces the pause flags; the only way to unpause
+    pub fn set_paused(&mut self, paused: PauseFlags) -> Result<()> {
         let config = &mut self.config;
         config.paused = paused;
 
-        emit!(PauseChanged { paused });
+        emit!(PauseChanged {
+            paused,
+            changed_by: self.admin.key(),
+        });
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl<'info> Pause<'info> {
+    /// Raises the given flags on top of those already set, never clears any
+    pub fn pause(&mut self, flags: PauseFlags) -> Result<()> {
+        let config = &mut self.config;
+        config.paused = config.paused.union(flags);
+
+        emit!(PauseChanged {
+            paused: config.paused,
+            changed_by: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
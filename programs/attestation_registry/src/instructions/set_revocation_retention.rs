@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AttestationRegistryError, event::RevocationRetentionChanged, state::Config};
+
+#[derive(Accounts)]
+pub struct SetRevocationRetention<'info> {
+    #[account(
+        mut,
+        seeds = [b"attest_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SetRevocationRetention<'info> {
+    pub fn set_revocation_retention(&mut self, revocation_retention_secs: i64) -> Result<()> {
+        let config = &mut self.config;
+
+        require!(!config.paused.admin_ops, AttestationRegistryError::Paused);
+        require!(
+            revocation_retention_secs >= 0,
+            AttestationRegistryError::InvalidExpiry
+        );
+
+        config.revocation_retention_secs = revocation_retention_secs;
+
+        emit!(RevocationRetentionChanged {
+            revocation_retention_secs
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    event::{AttestationClosed, AttestationRevoked},
+    state::{Attestation, AttestationHistory, Config, RevocationReason, SchemaDefinition},
+};
+
+#[derive(Accounts)]
+pub struct RevokeMyAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub subject: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &attestation.schema_id.to_le_bytes(),
+            attestation.issuer.as_ref()
+        ],
+        bump = attestation.bump,
+        constraint = attestation.subject == subject.key() @ AttestationRegistryError::Unauthorized
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub subject: Signer<'info>,
+
+    #[account(
+        mut,
+        close = subject,
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &attestation.schema_id.to_le_bytes(),
+            attestation.issuer.as_ref()
+        ],
+        bump = attestation.bump,
+        constraint = attestation.subject == subject.key() @ AttestationRegistryError::Unauthorized
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [b"schema".as_ref(), &attestation.schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(
+        mut,
+        seeds = [b"attest_history", attestation.key().as_ref()],
+        bump = history.bump
+    )]
+    pub history: Option<Account<'info, AttestationHistory>>,
+}
+
+/*
+    Subjects own their credentials: they can withdraw an attestation at any time,
+    regardless of whether the schema lets issuers revoke, or delete it outright and
+    take back the rent.
+*/
+impl<'info> RevokeMyAttestation<'info> {
+    pub fn revoke_my_attestation(&mut self) -> Result<()> {
+        let attestation = &mut self.attestation;
+
+        require!(!self.config.paused.revocation, AttestationRegistryError::Paused);
+        require!(
+            !attestation.revoked,
+            AttestationRegistryError::AlreadyRevoked
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        attestation.revoked = true;
+        attestation.revoked_at = now;
+        attestation.revocation_reason = Some(RevocationReason::UserRequest);
+
+        emit!(AttestationRevoked {
+            subject: attestation.subject,
+            schema_id: attestation.schema_id,
+            issuer: attestation.issuer,
+            reason: RevocationReason::UserRequest,
+            revoked_at: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> CloseAttestation<'info> {
+    pub fn close_attestation(&mut self) -> Result<()> {
+        require!(!self.config.paused.revocation, AttestationRegistryError::Paused);
+
+        let now = Clock::get()?.unix_timestamp;
+        self.attestation.archive(self.history.as_mut(), now)?;
+        self.schema.track_closed()?;
+
+        emit!(AttestationClosed {
+            subject: self.attestation.subject,
+            schema_id: self.attestation.schema_id,
+            issuer: self.attestation.issuer,
+        });
+
+        Ok(())
+    }
+}
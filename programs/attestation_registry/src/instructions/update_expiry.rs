@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::AttestationRegistryError,
-    state::{Attestation, Config},
+    state::{Attestation, Config, SchemaDefinition},
     AttestationExpiryUpdated,
 };
 
@@ -22,7 +22,7 @@ pub struct UpdateExpiry<'info> {
         seeds = [
             b"attest",
             subject.key().as_ref(),
-            &[attestation.schema_id as u8],
+            &attestation.schema_id.to_le_bytes(),
             attestation.issuer.as_ref()
         ],
         bump = attestation.bump,
@@ -30,13 +30,19 @@ pub struct UpdateExpiry<'info> {
     )]
     pub attestation: Account<'info, Attestation>,
 
+    #[account(
+        seeds = [b"schema".as_ref(), &attestation.schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
     pub signer: Signer<'info>,
 }
 
 /*
     subject = Alice’s wallet pubkey
     issuer = a KYC provider
-    schema_id = SchemaType::IdentityVerified
+    schema_id = SchemaType::ZkPassIdentity.id()
     expiry_ts = 1738953600 (some future timestamp)
 
     The KYC provider verifies that Alice's identity has been verified, valid until this timestamp.
@@ -54,7 +60,7 @@ impl<'info> UpdateExpiry<'info> {
         let attestation = &mut self.attestation;
         let clock = Clock::get()?;
 
-        require!(!config.paused, AttestationRegistryError::Paused);
+        require!(!config.paused.posting, AttestationRegistryError::Paused);
 
         // Only issuer or admin can update
         let signer = self.signer.key();
@@ -63,15 +69,9 @@ impl<'info> UpdateExpiry<'info> {
             AttestationRegistryError::Unauthorized
         );
 
-        // Validate new expiry
-        require!(
-            new_expiry_ts > clock.unix_timestamp,
-            AttestationRegistryError::InvalidExpiry
-        );
-        require!(
-            new_expiry_ts <= clock.unix_timestamp + config.max_expiry_secs,
-            AttestationRegistryError::ExpiryTooFar
-        );
+        // Validate new expiry; an extension counts as a fresh lifetime from now
+        self.schema
+            .check_expiry(clock.unix_timestamp, new_expiry_ts)?;
 
         attestation.expiry_ts = new_expiry_ts;
 
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Attestation, Config, VerificationResult};
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct VerifyAttestation<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Subject identity
+    pub subject: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &schema_id.to_le_bytes(),
+            attestation.issuer.as_ref()
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    /// CHECK: the `IssuerSigningKey` recorded on the attestation, checked in
+    /// `Attestation::signing_key_retired_at`; any account for proof-backed attestations
+    pub signing_key: UncheckedAccount<'info>,
+}
+
+/*
+    Lets other programs trust an attestation without re-deriving seeds or
+    duplicating the revocation/expiry/issuer rules. The seeds pin subject and
+    schema; everything else is reported through the returned status rather than
+    an error, so callers can decide how strict to be.
+*/
+impl<'info> VerifyAttestation<'info> {
+    pub fn verify_attestation(&self) -> Result<VerificationResult> {
+        let attestation = &self.attestation;
+        let now = Clock::get()?.unix_timestamp;
+
+        Ok(VerificationResult {
+            status: attestation.status(
+                &self.config,
+                attestation.signing_key_retired_at(&self.signing_key)?.is_some(),
+                now,
+            ),
+            issuer: attestation.issuer,
+            claim_hash: attestation.claim_hash,
+            issued_at: attestation.issued_at,
+            expiry_ts: attestation.expiry_ts,
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AttestationRegistryError,
+    state::{Attestation, AttestationBatch, Config, IssuerSigningKey, SchemaDefinition},
+};
+
+#[derive(Accounts)]
+#[instruction(schema_id: u16)]
+pub struct VerifyInBatch<'info> {
+    #[account(
+        seeds = [b"attest_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"schema".as_ref(), &schema_id.to_le_bytes()],
+        bump = schema.bump
+    )]
+    pub schema: Account<'info, SchemaDefinition>,
+
+    #[account(
+        seeds = [b"batch", batch.issuer.as_ref(), &batch.batch_id.to_le_bytes()],
+        bump = batch.bump
+    )]
+    pub batch: Account<'info, AttestationBatch>,
+
+    #[account(address = batch.signing_key @ AttestationRegistryError::SigningKeyMismatch)]
+    pub signing_key: Account<'info, IssuerSigningKey>,
+
+    /// CHECK: Subject identity; must sign when the schema requires subject consent
+    pub subject: UncheckedAccount<'info>,
+
+    /// CHECK: where the leaf would be materialized; read in `verify_in_batch` if it exists
+    #[account(
+        seeds = [
+            b"attest",
+            subject.key().as_ref(),
+            &schema_id.to_le_bytes(),
+            batch.issuer.as_ref()
+        ],
+        bump
+    )]
+    pub attestation: UncheckedAccount<'info>,
+
+    /// CHECK: the attestation's tombstone; only checked for existence
+    #[account(
+        seeds = [b"attest_tombstone", attestation.key().as_ref()],
+        bump
+    )]
+    pub tombstone: UncheckedAccount<'info>,
+}
+
+impl<'info> VerifyInBatch<'info> {
+    pub fn verify_in_batch(
+        &self,
+        schema_id: u16,
+        claim_hash: [u8; 32],
+        expiry_ts: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        // Batched leaves carry no subject signature, so consent has to come from this call
+        if self.schema.requires_subject_consent {
+            require!(
+                self.subject.is_signer,
+                AttestationRegistryError::SubjectConsentRequired
+            );
+        }
+
+        check_batch_leaf(
+            &self.config,
+            &self.schema,
+            &self.batch,
+            &self.signing_key,
+            &self.subject.key(),
+            schema_id,
+            claim_hash,
+            expiry_ts,
+            &proof,
+        )?;
+
+        // Once a leaf is materialized its account is what the subject or issuer revokes,
+        // so a revoked or closed account must stop the leaf from verifying too
+        if self.attestation.data_is_empty() {
+            require!(
+                self.tombstone.data_is_empty(),
+                AttestationRegistryError::LeafRevoked
+            );
+        } else {
+            let attestation =
+                Attestation::try_deserialize(&mut &self.attestation.try_borrow_data()?[..])?;
+            require!(!attestation.revoked, AttestationRegistryError::LeafRevoked);
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates a leaf against a batch root along with the same issuer, schema and
+/// expiry rules `post_attestation` applies to individually posted attestations.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn check_batch_leaf(
+    config: &Config,
+    schema: &SchemaDefinition,
+    batch: &AttestationBatch,
+    signing_key: &IssuerSigningKey,
+    subject: &Pubkey,
+    schema_id: u16,
+    claim_hash: [u8; 32],
+    expiry_ts: i64,
+    proof: &[[u8; 32]],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let issuer_entry = config.enabled_issuer(&batch.issuer)?;
+    require!(
+        batch.issuer_epoch == issuer_entry.revocation_epoch,
+        AttestationRegistryError::RevokedByIssuerEpoch
+    );
+    require!(
+        !signing_key.retired,
+        AttestationRegistryError::RevokedBySigningKey
+    );
+
+    require!(
+        !schema.deprecated,
+        AttestationRegistryError::SchemaDeprecated
+    );
+    if let Some(required_issuer_type) = schema.required_issuer_type {
+        require!(
+            issuer_entry.issuer_type == required_issuer_type,
+            AttestationRegistryError::UnsupportedIssuerType
+        );
+    }
+    require!(
+        issuer_entry.is_schema_allowed(schema_id),
+        AttestationRegistryError::IssuerSchemaNotAuthorized
+    );
+
+    require!(expiry_ts > now, AttestationRegistryError::InvalidExpiry);
+    schema.check_expiry(batch.posted_at, expiry_ts)?;
+
+    let leaf = AttestationBatch::leaf(subject, schema_id, &claim_hash, expiry_ts);
+    require!(
+        batch.verify_proof(leaf, proof),
+        AttestationRegistryError::InvalidMerkleProof
+    );
+
+    Ok(())
+}
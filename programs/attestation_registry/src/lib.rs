@@ -1,9 +1,12 @@
-#![allow(unexpected_cfgs, deprecated)]
+#![allow(unexpected_cfgs, deprecated, clippy::too_many_arguments)]
 pub mod constant;
 pub mod error;
 pub mod event;
+pub mod groth16;
 pub mod instructions;
 pub mod state;
+#[cfg(feature = "cpi")]
+pub mod verify;
 
 use anchor_lang::prelude::*;
 pub use constant::*;
@@ -20,8 +23,9 @@ pub mod attestation_registry {
 
     pub fn initialize_config(ctx: Context<InitializeConfig>, max_expiry: u64) -> Result<()> {
         let bump = ctx.bumps.config;
-        ctx.accounts
-            .initialize_config(max_expiry.try_into().unwrap(), bump)
+        let max_expiry = i64::try_from(max_expiry)
+            .map_err(|_| error!(AttestationRegistryError::InvalidExpiry))?;
+        ctx.accounts.initialize_config(max_expiry, bump)
     }
 
     pub fn add_issuer(ctx: Context<ManageIssuer>, issuer: Pubkey, issuer_type: IssuerType) -> Result<()> {
@@ -32,6 +36,83 @@ pub mod attestation_registry {
         ctx.accounts.remove_issuer(issuer)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_issuer_key(
+        ctx: Context<AddIssuerKey>,
+        issuer: Pubkey,
+        key_id: u16,
+        key_type: KeyType,
+        public_key: [u8; 65],
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        let bump = ctx.bumps.signing_key;
+        ctx.accounts.add_issuer_key(
+            issuer,
+            key_id,
+            key_type,
+            public_key,
+            valid_from,
+            valid_until,
+            bump,
+        )
+    }
+
+    pub fn rotate_issuer_key(
+        ctx: Context<RotateIssuerKey>,
+        new_key_id: u16,
+        key_type: KeyType,
+        public_key: [u8; 65],
+        grace_secs: i64,
+    ) -> Result<()> {
+        let bump = ctx.bumps.new_key;
+        ctx.accounts
+            .rotate_issuer_key(new_key_id, key_type, public_key, grace_secs, bump)
+    }
+
+    pub fn retire_issuer_key(ctx: Context<RetireIssuerKey>) -> Result<()> {
+        ctx.accounts.retire_issuer_key()
+    }
+
+    pub fn submit_issuer_application(
+        ctx: Context<SubmitIssuerApplication>,
+        issuer: Pubkey,
+        issuer_type: IssuerType,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
+        bond_amount: u64,
+    ) -> Result<()> {
+        let bump = ctx.bumps.application;
+        ctx.accounts.submit_issuer_application(
+            issuer,
+            issuer_type,
+            metadata_uri,
+            metadata_hash,
+            bond_amount,
+            bump,
+        )
+    }
+
+    pub fn approve_issuer_application(ctx: Context<ApproveIssuerApplication>) -> Result<()> {
+        ctx.accounts.approve_issuer_application()
+    }
+
+    pub fn reject_issuer_application(ctx: Context<RejectIssuerApplication>) -> Result<()> {
+        ctx.accounts.reject_issuer_application()
+    }
+
+    pub fn withdraw_issuer_application(ctx: Context<WithdrawIssuerApplication>) -> Result<()> {
+        ctx.accounts.withdraw_issuer_application()
+    }
+
+    pub fn slash_issuer_bond(ctx: Context<SlashIssuerBond>, amount: u64) -> Result<()> {
+        ctx.accounts.slash_issuer_bond(amount)
+    }
+
+    pub fn release_issuer_bond(ctx: Context<ReleaseIssuerBond>) -> Result<()> {
+        ctx.accounts.release_issuer_bond()
+    }
+
     pub fn set_issuer_status(
         ctx: Context<ManageIssuer>,
         issuer: Pubkey,
@@ -40,48 +121,318 @@ pub mod attestation_registry {
         ctx.accounts.set_issuer_status(issuer, enabled)
     }
 
-    pub fn add_schema(ctx: Context<ManageSchema>, schema: SchemaType) -> Result<()> {
-        ctx.accounts.add_schema(schema)
+    pub fn allow_issuer_schema(
+        ctx: Context<ManageIssuer>,
+        issuer: Pubkey,
+        schema_id: u16,
+    ) -> Result<()> {
+        ctx.accounts.allow_issuer_schema(issuer, schema_id)
+    }
+
+    pub fn disallow_issuer_schema(
+        ctx: Context<ManageIssuer>,
+        issuer: Pubkey,
+        schema_id: u16,
+    ) -> Result<()> {
+        ctx.accounts.disallow_issuer_schema(issuer, schema_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn revoke_issuer_attestations(
+        ctx: Context<ManageIssuer>,
+        issuer: Pubkey,
+        reason: RevocationReason,
+    ) -> Result<()> {
+        ctx.accounts.revoke_issuer_attestations(issuer, reason)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_schema(
+        ctx: Context<AddSchema>,
+        schema_id: u16,
+        name: String,
+        version: u16,
+        field_layout_hash: [u8; 32],
+        default_expiry_secs: i64,
+        revocable: bool,
+        requires_subject_consent: bool,
+        max_data_len: u16,
+        required_issuer_type: Option<IssuerType>,
+    ) -> Result<()> {
+        let bump = ctx.bumps.schema;
+        ctx.accounts.add_schema(
+            schema_id,
+            name,
+            version,
+            field_layout_hash,
+            default_expiry_secs,
+            revocable,
+            requires_subject_consent,
+            max_data_len,
+            required_issuer_type,
+            bump,
+        )
+    }
+
+    pub fn set_schema_quorum(
+        ctx: Context<SetSchemaQuorum>,
+        schema_id: u16,
+        mode: QuorumMode,
+        threshold: u8,
+        issuers: Vec<Pubkey>,
+    ) -> Result<()> {
+        let bump = ctx.bumps.quorum;
+        ctx.accounts
+            .set_schema_quorum(schema_id, mode, threshold, issuers, bump)
+    }
+
+    pub fn check_quorum<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckQuorum<'info>>,
+        schema_id: u16,
+    ) -> Result<QuorumResult> {
+        ctx.accounts.check_quorum(schema_id, ctx.remaining_accounts)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_verifying_key(
+        ctx: Context<SetVerifyingKey>,
+        schema_id: u16,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        public_inputs: u8,
+        ic: Vec<[u8; 64]>,
+        subject_input_index: u8,
+        timestamp_input_index: u8,
+    ) -> Result<()> {
+        let bump = ctx.bumps.verifying_key;
+        ctx.accounts.set_verifying_key(
+            schema_id,
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            public_inputs,
+            ic,
+            subject_input_index,
+            timestamp_input_index,
+            bump,
+        )
+    }
+
+    pub fn extend_verifying_key(
+        ctx: Context<ExtendVerifyingKey>,
+        schema_id: u16,
+        ic: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        ctx.accounts.extend_verifying_key(schema_id, ic)
+    }
+
+    pub fn deprecate_schema(ctx: Context<ManageSchema>, _schema_id: u16) -> Result<()> {
+        ctx.accounts.deprecate_schema()
+    }
+
+    pub fn set_schema_expiry_bounds(
+        ctx: Context<ManageSchema>,
+        _schema_id: u16,
+        min_expiry_secs: i64,
+        default_expiry_secs: i64,
+        max_expiry_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_schema_expiry_bounds(min_expiry_secs, default_expiry_secs, max_expiry_secs)
+    }
+
+    pub fn remove_schema(ctx: Context<ManageSchema>, _schema_id: u16) -> Result<()> {
+        ctx.accounts.remove_schema()
     }
 
     pub fn set_max_expiry(ctx: Context<SetMaxExpiry>, max_expiry: u64) -> Result<()> {
-        ctx.accounts.set_max_expiry(max_expiry.try_into().unwrap())
+        let max_expiry = i64::try_from(max_expiry)
+            .map_err(|_| error!(AttestationRegistryError::InvalidExpiry))?;
+        ctx.accounts.set_max_expiry(max_expiry)
+    }
+
+    pub fn set_revocation_retention(
+        ctx: Context<SetRevocationRetention>,
+        revocation_retention_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_revocation_retention(revocation_retention_secs)
     }
 
-    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    pub fn set_issuer_bond(
+        ctx: Context<SetIssuerBond>,
+        bond_mint: Pubkey,
+        min_issuer_bond: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_issuer_bond(bond_mint, min_issuer_bond)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: PauseFlags) -> Result<()> {
         ctx.accounts.set_paused(paused)
     }
 
+    pub fn pause(ctx: Context<Pause>, flags: PauseFlags) -> Result<()> {
+        ctx.accounts.pause(flags)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        ctx.accounts.set_guardian(new_guardian)
+    }
+
     pub fn set_admin(ctx: Context<SetAdmin>, new_admin: Pubkey) -> Result<()> {
         ctx.accounts.set_admin(new_admin)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn post_attestation(
         ctx: Context<PostAttestation>,
-        schema_id: SchemaType,
+        schema_id: u16,
         claim_hash: [u8; 32],
+        data: Vec<u8>,
         expiry_ts: i64,
         signature_bytes: [u8; 64],
         recover_id: u8,
-        allocator_from_proof: [u8; 65],
-        bump: u8,
     ) -> Result<()> {
+        let bump = ctx.bumps.attestation;
+        let tombstone_bump = ctx.bumps.tombstone;
         ctx.accounts.post_attestation(
             schema_id,
             claim_hash,
+            data,
             expiry_ts,
             signature_bytes,
             recover_id,
-            allocator_from_proof,
+            bump,
+            tombstone_bump,
+        )
+    }
+
+    pub fn repost_attestation(
+        ctx: Context<RepostAttestation>,
+        schema_id: u16,
+        claim_hash: [u8; 32],
+        data: Vec<u8>,
+        expiry_ts: i64,
+        signature_bytes: [u8; 64],
+        recover_id: u8,
+    ) -> Result<()> {
+        ctx.accounts.repost_attestation(
+            schema_id,
+            claim_hash,
+            data,
+            expiry_ts,
+            signature_bytes,
+            recover_id,
+        )
+    }
+
+    pub fn post_attestation_batch(
+        ctx: Context<PostAttestationBatch>,
+        batch_id: u64,
+        merkle_root: [u8; 32],
+        leaf_count: u32,
+        signature_bytes: [u8; 64],
+        recover_id: u8,
+    ) -> Result<()> {
+        let bump = ctx.bumps.batch;
+        ctx.accounts.post_attestation_batch(
+            batch_id,
+            merkle_root,
+            leaf_count,
+            signature_bytes,
+            recover_id,
+            bump,
+        )
+    }
+
+    pub fn post_zk_attestation(
+        ctx: Context<PostZkAttestation>,
+        schema_id: u16,
+        public_inputs: Vec<[u8; 32]>,
+        proof_a: [u8; 64],
+        proof_b: [u8; 128],
+        proof_c: [u8; 64],
+        expiry_ts: i64,
+    ) -> Result<()> {
+        let bump = ctx.bumps.attestation;
+        ctx.accounts.post_zk_attestation(
+            schema_id,
+            public_inputs,
+            proof_a,
+            proof_b,
+            proof_c,
+            expiry_ts,
             bump,
         )
     }
-    
-    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
-        ctx.accounts.revoke_attestation()
+
+    pub fn enable_attestation_history(ctx: Context<EnableAttestationHistory>) -> Result<()> {
+        let bump = ctx.bumps.history;
+        ctx.accounts.enable_attestation_history(bump)
+    }
+
+    pub fn verify_in_batch(
+        ctx: Context<VerifyInBatch>,
+        schema_id: u16,
+        claim_hash: [u8; 32],
+        expiry_ts: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .verify_in_batch(schema_id, claim_hash, expiry_ts, proof)
+    }
+
+    pub fn materialize_attestation(
+        ctx: Context<MaterializeAttestation>,
+        schema_id: u16,
+        claim_hash: [u8; 32],
+        expiry_ts: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let bump = ctx.bumps.attestation;
+        let tombstone_bump = ctx.bumps.tombstone;
+        ctx.accounts.materialize_attestation(
+            schema_id,
+            claim_hash,
+            expiry_ts,
+            proof,
+            bump,
+            tombstone_bump,
+        )
+    }
+
+    pub fn revoke_attestation(
+        ctx: Context<RevokeAttestation>,
+        reason: RevocationReason,
+    ) -> Result<()> {
+        ctx.accounts.revoke_attestation(reason)
+    }
+
+    pub fn revoke_my_attestation(ctx: Context<RevokeMyAttestation>) -> Result<()> {
+        ctx.accounts.revoke_my_attestation()
+    }
+
+    pub fn close_attestation(ctx: Context<CloseAttestation>) -> Result<()> {
+        ctx.accounts.close_attestation()
+    }
+
+    pub fn close_expired_attestation(ctx: Context<CloseExpiredAttestation>) -> Result<()> {
+        ctx.accounts.close_expired_attestation()
     }
 
     pub fn update_expiry(ctx: Context<UpdateExpiry>, new_expiry: u64) -> Result<()> {
-        ctx.accounts.update_expiry(new_expiry.try_into().unwrap())
+        let new_expiry = i64::try_from(new_expiry)
+            .map_err(|_| error!(AttestationRegistryError::InvalidExpiry))?;
+        ctx.accounts.update_expiry(new_expiry)
+    }
+
+    pub fn verify_attestation(
+        ctx: Context<VerifyAttestation>,
+        _schema_id: u16,
+    ) -> Result<VerificationResult> {
+        ctx.accounts.verify_attestation()
     }
 }
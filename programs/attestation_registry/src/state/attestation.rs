@@ -1,16 +1,136 @@
 use anchor_lang::prelude::*;
 
-use crate::SchemaType;
+use crate::{
+    error::AttestationRegistryError, AttestationHistory, AttestationStatus, Config, Issuer,
+    IssuerSigningKey, RevocationReason, ANCHOR_DISCRIMINATOR,
+};
 
 #[account]
 #[derive(InitSpace)]
 pub struct Attestation {
     pub subject: Pubkey,
-    pub schema_id: SchemaType,
+    pub schema_id: u16,
     pub claim_hash: [u8; 32], // Commitment (NO PII)
     pub issuer: Pubkey,
     pub issued_at: i64,
     pub expiry_ts: i64,
     pub revoked: bool,
     pub bump: u8,
+    pub revoked_at: i64,
+    pub revocation_reason: Option<RevocationReason>,
+    pub issuer_epoch: u32,   // Issuer's revocation epoch at issuance
+    pub payer: Pubkey,       // Funded the account; receives the rent back on close
+    pub signing_key: Pubkey, // `IssuerSigningKey` that signed this attestation
+    pub zk_verified: bool,   // Self-posted with a Groth16 proof; `issuer` is the verifying key
+    pub version: u32,        // Bumped every time the attestation is (re-)posted
+
+    // Re-posts must record the replaced version in `AttestationHistory`
+    pub history_enabled: bool,
+
+    // Schema-typed payload, sized per attestation rather than by `INIT_SPACE`
+    #[max_len(0)]
+    pub data: Vec<u8>,
+}
+
+impl Attestation {
+    pub fn space(data_len: usize) -> usize {
+        ANCHOR_DISCRIMINATOR + Self::INIT_SPACE + data_len
+    }
+
+    /// Expired, or revoked (individually, in bulk or through its signing key) for
+    /// longer than the retention window. `key_retired_at` comes from
+    /// `signing_key_retired_at`.
+    pub fn is_closable(&self, config: &Config, key_retired_at: Option<i64>, now: i64) -> bool {
+        if now >= self.expiry_ts {
+            return true;
+        }
+        if self.status(config, key_retired_at.is_some(), now) != AttestationStatus::Revoked {
+            return false;
+        }
+
+        // Retention runs from the earliest revocation that applies
+        let issuer = config.issuers.iter().find(|i| i.pubkey == self.issuer);
+        let revoked_at = [
+            self.revoked.then_some(self.revoked_at),
+            issuer
+                .filter(|i| !self.zk_verified && self.issuer_epoch != i.revocation_epoch)
+                .map(|i| i.revoked_at),
+            key_retired_at,
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(i64::MAX);
+
+        now >= revoked_at.saturating_add(config.revocation_retention_secs)
+    }
+
+    /// Snapshots the current version into `history` before a re-post overwrites it
+    /// or the account is closed. Fresh accounts (version 0) have nothing to preserve.
+    pub fn archive(
+        &self,
+        history: Option<&mut Account<AttestationHistory>>,
+        now: i64,
+    ) -> Result<()> {
+        if self.version == 0 || !self.history_enabled {
+            return Ok(());
+        }
+
+        let history = history.ok_or(AttestationRegistryError::HistoryAccountRequired)?;
+        history.record(self, now);
+
+        Ok(())
+    }
+
+    /// Revoked individually, or in bulk by the issuer's epoch moving past this attestation's
+    pub fn is_revoked(&self, issuer: &Issuer) -> bool {
+        self.revoked || self.issuer_epoch != issuer.revocation_epoch
+    }
+
+    /// When the key that signed this attestation was retired, if it has been.
+    /// `signing_key` must be the account recorded on the attestation; proof-backed
+    /// attestations record none, so any account passes for them.
+    pub fn signing_key_retired_at(&self, signing_key: &AccountInfo) -> Result<Option<i64>> {
+        if self.zk_verified {
+            return Ok(None);
+        }
+
+        require_keys_eq!(
+            signing_key.key(),
+            self.signing_key,
+            AttestationRegistryError::SigningKeyMismatch
+        );
+        let signing_key = IssuerSigningKey::try_deserialize(&mut &signing_key.try_borrow_data()?[..])?;
+
+        Ok(signing_key.retired.then_some(signing_key.retired_at))
+    }
+
+    /// Single source of truth for whether an attestation can be relied on right now.
+    /// `key_retired` comes from `signing_key_retired_at`.
+    pub fn status(&self, config: &Config, key_retired: bool, now: i64) -> AttestationStatus {
+        // Proof-backed attestations have no registered issuer to consult
+        if self.zk_verified {
+            return if self.revoked {
+                AttestationStatus::Revoked
+            } else if now >= self.expiry_ts {
+                AttestationStatus::Expired
+            } else {
+                AttestationStatus::Valid
+            };
+        }
+
+        let Some(issuer) = config.issuers.iter().find(|i| i.pubkey == self.issuer) else {
+            return AttestationStatus::IssuerNotFound;
+        };
+
+        if !issuer.enabled {
+            AttestationStatus::IssuerDisabled
+        } else if self.is_revoked(issuer) || key_retired {
+            AttestationStatus::Revoked
+        } else if now >= self.expiry_ts {
+            AttestationStatus::Expired
+        } else {
+            AttestationStatus::Valid
+        }
+    }
 }
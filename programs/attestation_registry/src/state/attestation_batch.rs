@@ -0,0 +1,51 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+/// Merkle root over many (subject, schema, claim_hash, expiry) leaves posted by one issuer
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationBatch {
+    pub issuer: Pubkey,
+    pub batch_id: u64,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u32,
+    pub posted_at: i64,
+    pub bump: u8,
+    pub issuer_epoch: u32,
+    pub signing_key: Pubkey,
+}
+
+impl AttestationBatch {
+    pub const MAX_PROOF_DEPTH: usize = 32;
+
+    pub fn leaf(
+        subject: &Pubkey,
+        schema_id: u16,
+        claim_hash: &[u8; 32],
+        expiry_ts: i64,
+    ) -> [u8; 32] {
+        keccak::hashv(&[
+            subject.as_ref(),
+            &schema_id.to_le_bytes(),
+            claim_hash,
+            &expiry_ts.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Sorted-pair proof check, so callers don't need to supply left/right positions
+    pub fn verify_proof(&self, leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+        if proof.len() > Self::MAX_PROOF_DEPTH {
+            return false;
+        }
+
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                keccak::hashv(&[&node, sibling]).to_bytes()
+            } else {
+                keccak::hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+
+        computed == self.merkle_root
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{Attestation, RevocationReason};
+
+/// Snapshot of an attestation version taken right before it is overwritten
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct HistoryEntry {
+    pub version: u32,
+    pub claim_hash: [u8; 32],
+    pub issuer: Pubkey,
+    pub issued_at: i64,
+    pub expiry_ts: i64,
+    pub revoked: bool,
+    pub revoked_at: i64,
+    pub revocation_reason: Option<RevocationReason>,
+    pub superseded_at: i64, // When the next version replaced this one, or it was closed
+}
+
+/// Append-only ring buffer of past versions of a single attestation
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationHistory {
+    pub attestation: Pubkey,
+    pub head: u16, // Slot the next entry is written to once the buffer is full
+    pub bump: u8,
+
+    #[max_len(16)]
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl AttestationHistory {
+    pub const MAX_ENTRIES: usize = 16;
+
+    /// Records the attestation's current version, evicting the oldest entry when full
+    pub fn record(&mut self, attestation: &Attestation, now: i64) {
+        let entry = HistoryEntry {
+            version: attestation.version,
+            claim_hash: attestation.claim_hash,
+            issuer: attestation.issuer,
+            issued_at: attestation.issued_at,
+            expiry_ts: attestation.expiry_ts,
+            revoked: attestation.revoked,
+            revoked_at: attestation.revoked_at,
+            revocation_reason: attestation.revocation_reason,
+            superseded_at: now,
+        };
+
+        if self.entries.len() < Self::MAX_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head as usize] = entry;
+            self.head = ((self.head as usize + 1) % Self::MAX_ENTRIES) as u16;
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AttestationRegistryError;
+
+/// Outlives the attestation it is seeded from and remembers the last version posted
+/// there. Closing an attestation resets its account, not this, so the next post has
+/// to be signed for a version no earlier signature covers.
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationTombstone {
+    pub attestation: Pubkey,
+    pub last_version: u32,
+    pub bump: u8,
+}
+
+impl AttestationTombstone {
+    /// Version the next post at this address has to be signed for
+    pub fn next_version(&self) -> Result<u32> {
+        self.last_version
+            .checked_add(1)
+            .ok_or(AttestationRegistryError::MathOverflow.into())
+    }
+}
@@ -1,23 +1,92 @@
 use anchor_lang::prelude::*;
 
-use crate::{Issuer, SchemaType};
+use crate::{error::AttestationRegistryError, Issuer, IssuerType};
 
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
     pub admin: Pubkey,
     pub max_expiry_secs: i64,
-    pub paused: bool,
+    pub paused: PauseFlags,
     pub bump: u8,
+    pub revocation_retention_secs: i64, // Grace period before revoked attestations can be closed
+
+    // Can raise pause flags during an incident, but only the admin can clear them
+    pub guardian: Pubkey,
+
+    // Token and minimum amount an `IssuerApplication` must lock up
+    pub bond_mint: Pubkey,
+    pub min_issuer_bond: u64,
 
     #[max_len(32)]
     pub issuers: Vec<Issuer>,
 
+    // Ids of every registered `SchemaDefinition`
     #[max_len(16)]
-    pub schemas: Vec<SchemaType>,
+    pub schemas: Vec<u16>,
 }
 
 impl Config {
     pub const MAX_ISSUERS: usize = 32;
     pub const MAX_SCHEMAS: usize = 16;
+
+    /// Looks up a registered issuer and fails unless it is currently enabled
+    pub fn enabled_issuer(&self, issuer: &Pubkey) -> Result<&Issuer> {
+        let issuer_entry = self
+            .issuers
+            .iter()
+            .find(|i| i.pubkey == *issuer)
+            .ok_or(AttestationRegistryError::IssuerNotFound)?;
+
+        require!(
+            issuer_entry.enabled,
+            AttestationRegistryError::IssuerDisabled
+        );
+
+        Ok(issuer_entry)
+    }
+
+    /// Adds a new, enabled issuer with no schema permissions yet
+    pub fn register_issuer(&mut self, issuer: Pubkey, issuer_type: IssuerType) -> Result<()> {
+        require!(
+            !self.issuers.iter().any(|i| i.pubkey == issuer),
+            AttestationRegistryError::IssuerAlreadyExists
+        );
+        require!(
+            self.issuers.len() < Self::MAX_ISSUERS,
+            AttestationRegistryError::TooManyIssuers
+        );
+
+        self.issuers.push(Issuer {
+            pubkey: issuer,
+            issuer_type,
+            enabled: true,
+            revocation_epoch: 0,
+            revoked_at: 0,
+            allowed_schemas: Vec::new(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Independently pausable areas of the registry. Incident-response actions
+/// (removing or disabling issuers, bulk revocation, key rotation, admin
+/// handover, unpausing) are never gated so a pause can't lock the admin out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct PauseFlags {
+    pub posting: bool,    // Posting, materializing and extending attestations
+    pub revocation: bool, // Revoking and closing attestations
+    pub admin_ops: bool,  // Routine configuration: issuers, schemas, keys, limits, applications
+}
+
+impl PauseFlags {
+    /// Flags set in either `self` or `other`
+    pub fn union(self, other: PauseFlags) -> PauseFlags {
+        PauseFlags {
+            posting: self.posting || other.posting,
+            revocation: self.revocation || other.revocation,
+            admin_ops: self.admin_ops || other.admin_ops,
+        }
+    }
 }
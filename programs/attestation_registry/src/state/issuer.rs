@@ -5,9 +5,23 @@ pub struct Issuer {
     pub pubkey: Pubkey,
     pub issuer_type: IssuerType,
     pub enabled: bool,
+
+    // Bumped to revoke every attestation this issuer has made so far
+    pub revocation_epoch: u32,
+    pub revoked_at: i64, // When `revocation_epoch` was last bumped
+
+    // Schemas this issuer is permitted to attest to
+    #[max_len(16)]
+    pub allowed_schemas: Vec<u16>,
 }
 
 impl Issuer {
+    pub const MAX_ALLOWED_SCHEMAS: usize = 16;
+
+    pub fn is_schema_allowed(&self, schema_id: u16) -> bool {
+        self.allowed_schemas.contains(&schema_id)
+    }
+
     pub fn get_eth_address(&self) -> [u8; 20] {
         let mut addr = [0u8; 20];
         addr.copy_from_slice(&self.pubkey.to_bytes()[0..20]);
@@ -34,4 +48,8 @@ impl Issuer {
 pub enum IssuerType {
     Solana = 0,    // Ed25519 signatures (Civic, Sismo, etc.)
     Ethereum = 1,  // ECDSA signatures (zkPass, etc.)
-}
\ No newline at end of file
+}
+
+impl IssuerType {
+    pub const COUNT: usize = 2;
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::IssuerType;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+    Released, // Issuer removed and the admin is done slashing; the bond can be withdrawn
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct IssuerApplication {
+    pub applicant: Pubkey, // Wallet that posted the bond and gets it back on rejection or withdrawal
+    pub issuer: Pubkey,    // Identity to register, same encoding as `Issuer::pubkey`
+    pub issuer_type: IssuerType,
+
+    // Off-chain description of the issuer, pinned by its hash
+    #[max_len(128)]
+    pub metadata_uri: String,
+    pub metadata_hash: [u8; 32],
+
+    pub bond_mint: Pubkey, // Snapshot of `Config::bond_mint` at submission
+    pub bond_amount: u64,
+    pub slashed_amount: u64,
+    pub status: ApplicationStatus,
+    pub submitted_at: i64,
+    pub reviewed_at: i64,
+    pub bump: u8,
+}
+
+impl IssuerApplication {
+    pub const MAX_METADATA_URI_LEN: usize = 128;
+
+    /// Bond still held in the vault after any slashing
+    pub fn remaining_bond(&self) -> u64 {
+        self.bond_amount.saturating_sub(self.slashed_amount)
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[repr(u8)]
+pub enum KeyType {
+    Ed25519 = 0,   // 32-byte key, verified through the Ed25519 native program
+    Secp256k1 = 1, // 65-byte uncompressed key, verified with secp256k1_recover
+}
+
+/// A key authorised to sign on behalf of an issuer. Keys can be rotated without
+/// touching the issuer's identity, so its existing attestations stay valid.
+#[account]
+#[derive(InitSpace)]
+pub struct IssuerSigningKey {
+    pub issuer: Pubkey,
+    pub key_id: u16,
+    pub key_type: KeyType,
+    pub public_key: [u8; 65], // Ed25519 keys use the first 32 bytes, the rest is zero
+    pub valid_from: i64,
+    pub valid_until: i64, // 0 = no end date
+    pub retired: bool, // Retired after a compromise; whatever it signed is revoked
+    pub retired_at: i64,
+    pub bump: u8,
+}
+
+impl IssuerSigningKey {
+    pub fn is_active(&self, now: i64) -> bool {
+        !self.retired && now >= self.valid_from && (self.valid_until == 0 || now < self.valid_until)
+    }
+
+    pub fn is_well_formed(key_type: KeyType, public_key: &[u8; 65]) -> bool {
+        match key_type {
+            KeyType::Ed25519 => public_key[32..].iter().all(|b| *b == 0),
+            KeyType::Secp256k1 => public_key[0] == 4,
+        }
+    }
+}
@@ -1,9 +1,29 @@
 pub mod schema_type;
+pub mod schema_definition;
+pub mod schema_quorum;
+pub mod schema_verifying_key;
 pub mod attestation;
+pub mod attestation_history;
+pub mod attestation_tombstone;
+pub mod attestation_batch;
 pub mod issuer;
+pub mod issuer_signing_key;
+pub mod issuer_application;
 pub mod config;
+pub mod revocation_reason;
+pub mod verification;
 
 pub use schema_type::*;
+pub use schema_definition::*;
+pub use schema_quorum::*;
+pub use schema_verifying_key::*;
 pub use attestation::*;
+pub use attestation_history::*;
+pub use attestation_tombstone::*;
+pub use attestation_batch::*;
 pub use issuer::*;
+pub use issuer_signing_key::*;
+pub use issuer_application::*;
 pub use config::*;
+pub use revocation_reason::*;
+pub use verification::*;
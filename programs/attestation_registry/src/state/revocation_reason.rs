@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[repr(u8)]
+pub enum RevocationReason {
+    Fraud = 0,
+    DataUpdate = 1,
+    UserRequest = 2,
+    KeyCompromise = 3,
+}
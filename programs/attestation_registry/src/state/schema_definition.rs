@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AttestationRegistryError, IssuerType};
+
+#[account]
+#[derive(InitSpace)]
+pub struct SchemaDefinition {
+    pub schema_id: u16,
+    #[max_len(32)]
+    pub name: String,
+    pub version: u16,
+    pub field_layout_hash: [u8; 32], // Hash of the off-chain field layout
+    pub default_expiry_secs: i64,    // Used when an attestation is posted without an expiry
+    pub min_expiry_secs: i64,        // Shortest lifetime an attestation may be issued with
+    pub max_expiry_secs: i64,        // Longest lifetime, capped by `Config::max_expiry_secs`
+    pub revocable: bool,
+    pub requires_subject_consent: bool, // Subject must co-sign issuance
+    pub max_data_len: u16, // Max payload size; 0 disables payloads
+    pub required_issuer_type: Option<IssuerType>,
+    pub deprecated: bool,
+    pub created_at: i64,
+    pub bump: u8,
+    pub attestation_count: u32, // Open `Attestation` accounts; the schema can't be removed until they're closed
+}
+
+impl SchemaDefinition {
+    pub const MAX_NAME_LEN: usize = 32;
+
+    /// `min <= default <= max` with a positive default and `max` under the registry ceiling
+    pub fn validate_expiry_bounds(
+        min_expiry_secs: i64,
+        default_expiry_secs: i64,
+        max_expiry_secs: i64,
+        ceiling_secs: i64,
+    ) -> Result<()> {
+        require!(
+            min_expiry_secs >= 0
+                && default_expiry_secs > 0
+                && min_expiry_secs <= default_expiry_secs
+                && default_expiry_secs <= max_expiry_secs,
+            AttestationRegistryError::InvalidExpiry
+        );
+        require!(
+            max_expiry_secs <= ceiling_secs,
+            AttestationRegistryError::ExpiryTooFar
+        );
+
+        Ok(())
+    }
+
+    pub fn track_opened(&mut self) -> Result<()> {
+        self.attestation_count = self
+            .attestation_count
+            .checked_add(1)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn track_closed(&mut self) -> Result<()> {
+        self.attestation_count = self
+            .attestation_count
+            .checked_sub(1)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Checks that an attestation issued at `issued_at` and expiring at `expiry_ts`
+    /// lives within this schema's bounds
+    pub fn check_expiry(&self, issued_at: i64, expiry_ts: i64) -> Result<()> {
+        let lifetime = expiry_ts
+            .checked_sub(issued_at)
+            .ok_or(AttestationRegistryError::MathOverflow)?;
+
+        require!(
+            lifetime > 0 && lifetime >= self.min_expiry_secs,
+            AttestationRegistryError::ExpiryTooSoon
+        );
+        require!(
+            lifetime <= self.max_expiry_secs,
+            AttestationRegistryError::ExpiryTooFar
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::IssuerType;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[repr(u8)]
+pub enum QuorumMode {
+    NamedIssuers = 0,        // K of the listed issuers
+    DistinctIssuerTypes = 1, // K different `IssuerType`s, with Groth16 proofs as one more source
+}
+
+/// Per-schema requirement for how many independent attestations a subject needs
+#[account]
+#[derive(InitSpace)]
+pub struct SchemaQuorum {
+    pub schema_id: u16,
+    pub mode: QuorumMode,
+    pub threshold: u8,
+
+    // Only consulted in `QuorumMode::NamedIssuers`
+    #[max_len(8)]
+    pub issuers: Vec<Pubkey>,
+
+    pub bump: u8,
+}
+
+impl SchemaQuorum {
+    pub const MAX_ISSUERS: usize = 8;
+
+    // Every issuer type, plus proof-backed attestations which have no issuer
+    pub const MAX_DISTINCT_SOURCES: usize = IssuerType::COUNT + 1;
+}
+
+/// Returned by `check_quorum` through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct QuorumResult {
+    pub qualified: bool,
+    pub matched: u8,
+    pub threshold: u8,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+/// Well-known schema ids. New schemas are registered at runtime through
+/// `add_schema` and do not need an entry here.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 #[repr(u8)]
 pub enum SchemaType {
@@ -10,3 +12,9 @@ pub enum SchemaType {
     PlaidIncome = 4,
     Custom = 255,
 }
+
+impl SchemaType {
+    pub fn id(self) -> u16 {
+        self as u16
+    }
+}
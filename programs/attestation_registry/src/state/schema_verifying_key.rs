@@ -0,0 +1,63 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+use crate::error::AttestationRegistryError;
+
+/// Groth16 verifying key over BN254 for a proof-backed schema. Points use the
+/// big-endian, uncompressed encoding expected by the alt_bn128 syscalls.
+#[account]
+#[derive(InitSpace)]
+pub struct SchemaVerifyingKey {
+    pub schema_id: u16,
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+
+    // Inputs the circuit exposes; proofs are refused until `ic` covers all of them
+    pub public_inputs: u8,
+
+    // One point per public input, plus the constant term at index 0
+    #[max_len(9)]
+    pub ic: Vec<[u8; 64]>,
+
+    pub subject_input_index: u8,   // Must commit to the posting subject, see `subject_signal`
+    pub timestamp_input_index: u8, // Unix time the proof was generated at
+    pub bump: u8,
+}
+
+impl SchemaVerifyingKey {
+    pub const MAX_PUBLIC_INPUTS: usize = 8;
+
+    pub fn public_input_count(&self) -> usize {
+        self.ic.len().saturating_sub(1)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.ic.len() == self.public_inputs as usize + 1
+    }
+
+    /// The proof's timestamp input, which circuits expose as a big-endian field element
+    pub fn proof_timestamp(&self, public_inputs: &[[u8; 32]]) -> Result<i64> {
+        let input = public_inputs
+            .get(self.timestamp_input_index as usize)
+            .ok_or(AttestationRegistryError::InvalidPublicInputs)?;
+        require!(
+            input[..24].iter().all(|b| *b == 0),
+            AttestationRegistryError::InvalidPublicInputs
+        );
+
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(&input[24..]);
+        i64::try_from(u64::from_be_bytes(timestamp))
+            .map_err(|_| error!(AttestationRegistryError::InvalidPublicInputs))
+    }
+
+    /// keccak256 of the subject with the top three bits cleared so it always fits
+    /// in the BN254 scalar field. Circuits expose this as their signal input so a
+    /// proof can't be replayed by a different wallet.
+    pub fn subject_signal(subject: &Pubkey) -> [u8; 32] {
+        let mut signal = keccak::hash(subject.as_ref()).to_bytes();
+        signal[0] &= 0x1f;
+        signal
+    }
+}
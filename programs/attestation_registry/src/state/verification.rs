@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[repr(u8)]
+pub enum AttestationStatus {
+    Valid = 0,
+    Revoked = 1,
+    Expired = 2,
+    IssuerNotFound = 3,
+    IssuerDisabled = 4,
+}
+
+/// Returned by `verify_attestation` through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct VerificationResult {
+    pub status: AttestationStatus,
+    pub issuer: Pubkey,
+    pub claim_hash: [u8; 32],
+    pub issued_at: i64,
+    pub expiry_ts: i64,
+}
+
+impl VerificationResult {
+    pub fn is_valid(&self) -> bool {
+        self.status == AttestationStatus::Valid
+    }
+}
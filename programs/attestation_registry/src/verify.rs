@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{cpi, state::VerificationResult};
+
+/// CPI into `verify_attestation` and decode the returned result.
+///
+/// Enable the `cpi` feature of this crate to call it from another program.
+pub fn verify_attestation_cpi<'info>(
+    attestation_program: AccountInfo<'info>,
+    config: AccountInfo<'info>,
+    subject: AccountInfo<'info>,
+    attestation: AccountInfo<'info>,
+    signing_key: AccountInfo<'info>,
+    schema_id: u16,
+) -> Result<VerificationResult> {
+    let result = cpi::verify_attestation(
+        CpiContext::new(
+            attestation_program,
+            cpi::accounts::VerifyAttestation {
+                config,
+                subject,
+                attestation,
+                signing_key,
+            },
+        ),
+        schema_id,
+    )?;
+
+    Ok(result.get())
+}
@@ -1 +1,2 @@
 pub const ANCHOR_DISCRIMINATOR: usize = 8;
+pub const MAX_SCORE_MODELS: usize = 8;
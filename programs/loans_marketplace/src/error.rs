@@ -37,4 +37,19 @@ pub enum LoanMarketplaceErrorCode {
 
     #[msg("Exceeds loan amount")]
     ExceedsLoanAmount,
+
+    #[msg("Score attestation required")]
+    ScoreRequired,
+
+    #[msg("Score attestation does not cover this borrower and loan")]
+    InvalidScore,
+
+    #[msg("Score produced by a model that is not accepted")]
+    ScoreModelNotAccepted,
+
+    #[msg("Score attestation is revoked, expired or stale")]
+    StaleScore,
+
+    #[msg("Score attestation is under dispute")]
+    ScoreDisputed,
 }
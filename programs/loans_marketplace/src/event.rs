@@ -47,6 +47,11 @@ pub struct CollateralDeposited {
     pub total: u64,
 }
 
+#[event]
+pub struct ScoreModelsSet {
+    pub accepted_score_models: Vec<Pubkey>,
+}
+
 #[event]
 pub struct LenderPaidOut {
     pub lender: Pubkey,
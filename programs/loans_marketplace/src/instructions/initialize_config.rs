@@ -30,6 +30,7 @@ impl<'info> InitializeConfig<'info> {
             fee_bps,
             usdc_mint: self.usdc_mint.key(),
             bump: config_bump,
+            accepted_score_models: Vec::new(),
         });
 
         Ok(())
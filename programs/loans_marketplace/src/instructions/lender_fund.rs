@@ -5,6 +5,7 @@ use crate::state::LoanAccount;
 use crate::{LenderFunded, LenderShare, ANCHOR_DISCRIMINATOR};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use score_attestor::ScoreAttestation;
 
 #[derive(Accounts)]
 pub struct LenderFund<'info> {
@@ -39,6 +40,16 @@ pub struct LenderFund<'info> {
         space = ANCHOR_DISCRIMINATOR + LenderShare::INIT_SPACE
     )]
     pub lender_share: Account<'info, LenderShare>,
+    /// CHECK: the borrower's loan-specific score PDA under score_attestor, always passed so
+    /// it can't be skipped; read in `lender_fund` if it exists
+    #[account(
+        seeds = [b"score", loan.borrower.as_ref(), loan.key().as_ref()],
+        bump,
+        seeds::program = score_attestor::ID
+    )]
+    pub score: UncheckedAccount<'info>,
+    // Borrower's subject-level score, only counted while there is no loan-specific one
+    pub subject_score: Option<Account<'info, ScoreAttestation>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
@@ -54,11 +65,27 @@ impl<'info> LenderFund<'info> {
             LoanMarketplaceErrorCode::InvalidState
         );
 
+        let now = Clock::get()?.unix_timestamp;
         require!(
-            Clock::get()?.unix_timestamp <= loan.funding_deadline,
+            now <= loan.funding_deadline,
             LoanMarketplaceErrorCode::FundingExpired
         );
 
+        let loan_score = if self.score.data_is_empty() {
+            None
+        } else {
+            Some(ScoreAttestation::try_deserialize(
+                &mut &self.score.try_borrow_data()?[..],
+            )?)
+        };
+        self.config.check_score(
+            loan_score.as_ref(),
+            self.subject_score.as_deref(),
+            &loan.borrower,
+            &loan.key(),
+            now,
+        )?;
+
         let new_funded = loan
             .funded_amount
             .checked_add(amount)
@@ -73,7 +73,7 @@ impl<'info> PayoutLenders<'info> {
             &self.loan.loan_id.to_le_bytes(),
             &[self.loan.bump],
         ];
-        let signer = &[&seeds[..]];
+        let signer = &[seeds];
 
         let cpi_accounts = Transfer {
             from: self.collateral_escrow_ata.to_account_info(),
@@ -0,0 +1,33 @@
+use crate::{error::LoanMarketplaceErrorCode, state::Config, ScoreModelsSet, MAX_SCORE_MODELS};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetScoreModels<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SetScoreModels<'info> {
+    /// Replaces the accepted model list; an empty list turns the score requirement off
+    pub fn set_score_models(&mut self, accepted_score_models: Vec<Pubkey>) -> Result<()> {
+        require!(
+            accepted_score_models.len() <= MAX_SCORE_MODELS,
+            LoanMarketplaceErrorCode::InvalidParam
+        );
+
+        self.config.accepted_score_models = accepted_score_models.clone();
+
+        emit!(ScoreModelsSet {
+            accepted_score_models
+        });
+
+        Ok(())
+    }
+}
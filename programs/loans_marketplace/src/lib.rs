@@ -63,6 +63,13 @@ pub mod loans_marketplace {
         ctx.accounts.set_loan_for_default_testing(days_overdue)
     }
 
+    pub fn set_score_models(
+        ctx: Context<SetScoreModels>,
+        accepted_score_models: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.set_score_models(accepted_score_models)
+    }
+
     pub fn lender_fund(ctx: Context<LenderFund>, amount: u64) -> Result<()> {
         let lender_share_bump = ctx.bumps.lender_share;
         ctx.accounts.lender_fund(amount, lender_share_bump)
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use score_attestor::ScoreAttestation;
+
+use crate::{error::LoanMarketplaceErrorCode, MAX_SCORE_MODELS};
 
 /// Global config for protocol parameters
 #[account]
@@ -10,4 +13,59 @@ pub struct Config {
     pub fee_bps: u16,      // 2,
     pub usdc_mint: Pubkey, // 32
     pub bump: u8,          // 1
+
+    // score_attestor `ScoringModel` PDAs whose scores lenders accept, empty means no score is required
+    #[max_len(MAX_SCORE_MODELS)]
+    pub accepted_score_models: Vec<Pubkey>, // 4 + 32 * MAX_SCORE_MODELS
+}
+
+impl Config {
+    pub fn requires_score(&self) -> bool {
+        !self.accepted_score_models.is_empty()
+    }
+
+    /// Picks the loan-specific score when one exists, the borrower's subject-level
+    /// score otherwise, and checks that it covers this borrower and loan, is not
+    /// disputed, is still fresh and was produced by one of the accepted model versions.
+    /// `loan_score` must come from the `[b"score", borrower, loan]` PDA, so a stale or
+    /// disputed loan score can't be sidestepped with the subject-level one.
+    pub fn check_score(
+        &self,
+        loan_score: Option<&ScoreAttestation>,
+        subject_score: Option<&ScoreAttestation>,
+        borrower: &Pubkey,
+        loan: &Pubkey,
+        now: i64,
+    ) -> Result<()> {
+        if !self.requires_score() {
+            return Ok(());
+        }
+
+        let score = match (loan_score, subject_score) {
+            (Some(score), _) => {
+                require!(score.loan == *loan, LoanMarketplaceErrorCode::InvalidScore);
+                score
+            }
+            (None, Some(score)) => {
+                require!(
+                    score.is_subject_level(),
+                    LoanMarketplaceErrorCode::InvalidScore
+                );
+                score
+            }
+            (None, None) => return err!(LoanMarketplaceErrorCode::ScoreRequired),
+        };
+        require!(
+            score.subject == *borrower,
+            LoanMarketplaceErrorCode::InvalidScore
+        );
+        require!(!score.disputed, LoanMarketplaceErrorCode::ScoreDisputed);
+        require!(score.is_fresh(now), LoanMarketplaceErrorCode::StaleScore);
+        require!(
+            self.accepted_score_models.contains(&score.model),
+            LoanMarketplaceErrorCode::ScoreModelNotAccepted
+        );
+
+        Ok(())
+    }
 }
@@ -1,3 +1,15 @@
+use anchor_lang::prelude::*;
+
 pub const ANCHOR_DISCRIMINATOR: usize = 8;
 pub const MAX_ORACLES: usize = 16;
-pub const MAX_MODELS: usize = 32;
\ No newline at end of file
+pub const MAX_MODELS: usize = 32;
+pub const MAX_SCORE_HISTORY: usize = 32;
+pub const MAX_NONCE_FLOORS: usize = 8;
+pub const MAX_PD_BPS: u32 = 10_000;
+pub const MAX_COLLATERAL_BPS: u16 = 50_000;
+
+// loans_marketplace `LoanAccount`, read by hand since the marketplace depends on this crate
+pub const LOANS_MARKETPLACE_ID: Pubkey = pubkey!("5CsJHgdh6jtKRVJiJL4bBpTeUcUFV3B9gphEccyvGQmS");
+pub const LOAN_STATE_OFFSET: usize = 81; // discriminator, bump, borrower, loan_id, amount, term, apr, collateral, deadline
+pub const LOAN_STATE_DEFAULTED: u8 = 6;
+pub const LOAN_STATE_SETTLED: u8 = 7;
@@ -49,5 +49,32 @@ pub enum ScoreAttestorError {
     InvalidSignature,
 
     #[msg("Attestor unauthorized")]
-    UnauthorizedAttestor
+    UnauthorizedAttestor,
+
+    #[msg("Nonce must be greater than the last posted nonce")]
+    InvalidNonce,
+
+    #[msg("Score is still live and its loan is not settled or defaulted")]
+    CloseNotAllowed,
+
+    #[msg("Score is under dispute")]
+    ScoreDisputed,
+
+    #[msg("Score is not under dispute")]
+    NoActiveDispute,
+
+    #[msg("Only the admin or attestor can resolve disputes")]
+    UnauthorizedResolver,
+
+    #[msg("Invalid admin")]
+    InvalidAdmin,
+
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+
+    #[msg("This score payload has already been disputed")]
+    AlreadyDisputed,
+
+    #[msg("Amending a score needs its model and history accounts")]
+    AmendmentAccountsRequired,
 }
@@ -1,16 +1,31 @@
 use anchor_lang::prelude::*;
 
+use crate::DisputeResolution;
+
 #[event]
 pub struct ConfigInitialized {
     pub admin: Pubkey,
     pub attestor: Pubkey,
 }
 
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
 #[event]
 pub struct AdminChanged {
+    pub previous_admin: Pubkey,
     pub new_admin: Pubkey,
 }
 
+#[event]
+pub struct AttestorChanged {
+    pub previous_attestor: Pubkey,
+    pub new_attestor: Pubkey,
+}
+
 #[event]
 pub struct PausedSet {
     pub paused: bool,
@@ -25,6 +40,11 @@ pub struct ScorePosted {
     pub pd_bps: u32,
     pub recommended_min_collateral_bps: u16,
     pub expiry_ts: i64,
+    pub model: Pubkey,
+    pub model_version: u16,
+    pub nonce: u64,
+    pub posted_at: i64,
+    pub sequence: u64, // Position in the subject's full posting history
 }
 
 #[event]
@@ -39,3 +59,65 @@ pub struct ScoreExpiryUpdated {
     pub loan: Pubkey,
     pub new_expiry_ts: i64,
 }
+
+#[event]
+pub struct OracleAdded {
+    pub oracle: [u8; 65],
+}
+
+#[event]
+pub struct OracleRemoved {
+    pub oracle: [u8; 65],
+}
+
+#[event]
+pub struct OracleThresholdSet {
+    pub oracle_threshold: u8,
+}
+
+#[event]
+pub struct ScoreWindowsSet {
+    pub max_validity_secs: i64,
+    pub max_staleness_secs: i64,
+}
+
+#[event]
+pub struct ModelAdded {
+    pub model: Pubkey,
+    pub model_id: [u8; 32],
+    pub version: u16,
+    pub grade_scale: u8,
+    pub min_score: u16,
+    pub max_score: u16,
+    pub pd_calibration_hash: [u8; 32],
+}
+
+#[event]
+pub struct ModelStatusSet {
+    pub model: Pubkey,
+    pub model_id: [u8; 32],
+    pub version: u16,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct ScoreClosed {
+    pub subject: Pubkey,
+    pub loan: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct ScoreDisputed {
+    pub subject: Pubkey,
+    pub loan: Pubkey,
+    pub reason_hash: [u8; 32],
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub subject: Pubkey,
+    pub loan: Pubkey,
+    pub resolution: DisputeResolution,
+    pub resolved_by: Pubkey,
+}
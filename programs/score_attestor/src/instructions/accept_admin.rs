@@ -0,0 +1,31 @@
+use crate::{error::ScoreAttestorError, event::AdminChanged, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"score_config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ScoreAttestorError::NotPendingAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub new_admin: Signer<'info>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let cfg = &mut self.config;
+        let previous_admin = cfg.admin;
+
+        cfg.admin = self.new_admin.key();
+        cfg.pending_admin = None;
+
+        emit!(AdminChanged {
+            previous_admin,
+            new_admin: cfg.admin,
+        });
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
     error::ScoreAttestorError,
-    event::{AdminChanged, PausedSet},
+    event::{
+        AdminProposed, AttestorChanged, OracleAdded, OracleRemoved, OracleThresholdSet, PausedSet,
+        ScoreWindowsSet,
+    },
     state::Config,
+    MAX_ORACLES,
 };
 use anchor_lang::prelude::*;
 
@@ -19,9 +23,19 @@ pub struct AdminOnly<'info> {
 }
 
 impl<'info> AdminOnly<'info> {
-    pub fn set_admin(&mut self, new_admin: Pubkey) -> Result<()> {
-        self.config.admin = new_admin;
-        emit!(AdminChanged { new_admin });
+    /// First half of the handover, `accept_admin` completes it. A new proposal replaces
+    /// any pending one.
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        require!(
+            new_admin != Pubkey::default() && new_admin != self.config.admin,
+            ScoreAttestorError::InvalidAdmin
+        );
+
+        self.config.pending_admin = Some(new_admin);
+        emit!(AdminProposed {
+            admin: self.config.admin,
+            pending_admin: new_admin,
+        });
         Ok(())
     }
 
@@ -33,13 +47,78 @@ impl<'info> AdminOnly<'info> {
 
     pub fn set_issuer(&mut self, issuer: Pubkey) -> Result<()> {
         require!(!self.config.paused, ScoreAttestorError::Paused);
+        require!(issuer != Pubkey::default(), ScoreAttestorError::InvalidParam);
+
+        let previous_attestor = self.config.attestor;
         self.config.attestor = issuer;
+        emit!(AttestorChanged {
+            previous_attestor,
+            new_attestor: issuer,
+        });
         Ok(())
     }
 
-    pub fn set_secp256k1_pubkey(&mut self, secp256k1_pubkey: [u8; 65]) -> Result<()> {
+    pub fn add_oracle(&mut self, oracle: [u8; 65]) -> Result<()> {
         require!(!self.config.paused, ScoreAttestorError::Paused);
-        self.config.secp256k1_pubkey = secp256k1_pubkey;
+        require!(oracle[0] == 4, ScoreAttestorError::InvalidParam); // uncompressed key
+
+        let cfg = &mut self.config;
+        require!(!cfg.is_oracle(&oracle), ScoreAttestorError::OracleExists);
+        require!(cfg.oracles.len() < MAX_ORACLES, ScoreAttestorError::TooManyOracles);
+
+        cfg.oracles.push(oracle);
+        emit!(OracleAdded { oracle });
+        Ok(())
+    }
+
+    /// Fails if the remaining set could no longer meet the threshold
+    pub fn remove_oracle(&mut self, oracle: [u8; 65]) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+
+        let cfg = &mut self.config;
+        let pos = cfg
+            .oracles
+            .iter()
+            .position(|o| *o == oracle)
+            .ok_or(ScoreAttestorError::OracleNotFound)?;
+        require!(
+            cfg.oracles.len() > cfg.oracle_threshold as usize,
+            ScoreAttestorError::InvalidOracleThreshold
+        );
+
+        cfg.oracles.remove(pos);
+        emit!(OracleRemoved { oracle });
+        Ok(())
+    }
+
+    pub fn set_oracle_threshold(&mut self, oracle_threshold: u8) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+        require!(
+            oracle_threshold > 0 && oracle_threshold as usize <= self.config.oracles.len(),
+            ScoreAttestorError::InvalidOracleThreshold
+        );
+
+        self.config.oracle_threshold = oracle_threshold;
+        emit!(OracleThresholdSet { oracle_threshold });
+        Ok(())
+    }
+
+    pub fn set_score_windows(
+        &mut self,
+        max_validity_secs: i64,
+        max_staleness_secs: i64,
+    ) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+        require!(max_validity_secs > 0, ScoreAttestorError::InvalidExpiry);
+        require!(max_staleness_secs > 0, ScoreAttestorError::InvalidMaxStaleness);
+
+        self.config.max_validity_secs = max_validity_secs;
+        self.config.max_staleness_secs = max_staleness_secs;
+
+        emit!(ScoreWindowsSet {
+            max_validity_secs,
+            max_staleness_secs
+        });
         Ok(())
     }
 }
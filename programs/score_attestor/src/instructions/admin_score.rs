@@ -21,7 +21,7 @@ pub struct AdminAndScore<'info> {
     /// CHECK: subject identity (pubkey only)
     pub subject: UncheckedAccount<'info>,
 
-    /// CHECK: loan identity (pubkey only)
+    /// CHECK: loan identity (pubkey only), the model for subject-level scores
     pub loan: UncheckedAccount<'info>,
 
     #[account(
@@ -29,7 +29,7 @@ pub struct AdminAndScore<'info> {
         seeds = [b"score", subject.key().as_ref(), loan.key().as_ref()],
         bump = score.bump,
         constraint = score.subject == subject.key(),
-        constraint = score.loan == loan.key()
+        constraint = score.scope() == loan.key()
     )]
     pub score: Account<'info, ScoreAttestation>,
 }
@@ -52,7 +52,10 @@ impl<'info> AdminAndScore<'info> {
         require!(!self.config.paused, ScoreAttestorError::Paused);
 
         let now = Clock::get()?.unix_timestamp;
-        require!(new_expiry_ts > now, ScoreAttestorError::InvalidExpiry);
+        require!(
+            new_expiry_ts > now && new_expiry_ts - now <= self.config.max_validity_secs,
+            ScoreAttestorError::InvalidExpiry
+        );
 
         self.score.expiry_ts = new_expiry_ts;
 
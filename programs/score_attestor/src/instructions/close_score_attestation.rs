@@ -0,0 +1,90 @@
+use crate::{
+    error::ScoreAttestorError,
+    event::ScoreClosed,
+    state::{Config, ScoreAttestation, ScoreHistory},
+    LOANS_MARKETPLACE_ID, LOAN_STATE_DEFAULTED, LOAN_STATE_OFFSET, LOAN_STATE_SETTLED,
+};
+use anchor_lang::{prelude::*, solana_program::hash};
+
+#[derive(Accounts)]
+pub struct CloseScoreAttestation<'info> {
+    #[account(
+        seeds = [b"score_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub closer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"score", score.subject.as_ref(), score.scope().as_ref()],
+        bump = score.bump,
+        has_one = payer
+    )]
+    pub score: Account<'info, ScoreAttestation>,
+
+    /// CHECK: rent recipient, checked against `score.payer`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"score_history", score.subject.as_ref()],
+        bump = history.bump
+    )]
+    pub history: Account<'info, ScoreHistory>,
+
+    /// CHECK: marketplace LoanAccount, only needed when the closer is not the attestor
+    pub loan: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CloseScoreAttestation<'info> {
+    /// The attestor may close dead scores; anyone may close a loan score once the loan is over
+    pub fn close_score_attestation(&mut self) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let score = &self.score;
+
+        let attestor_close = self.closer.key() == self.config.attestor
+            && (score.revoked || now >= score.expiry_ts);
+        require!(
+            attestor_close || self.loan_is_closed()?,
+            ScoreAttestorError::CloseNotAllowed
+        );
+
+        self.history.raise_nonce_floor(score.scope(), score.nonce);
+
+        emit!(ScoreClosed {
+            subject: score.subject,
+            loan: score.loan,
+            closed_by: self.closer.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Whether `loan` is the score's marketplace loan and it is Settled or Defaulted
+    fn loan_is_closed(&self) -> Result<bool> {
+        let Some(loan) = &self.loan else {
+            return Ok(false);
+        };
+        if self.score.is_subject_level()
+            || loan.key() != self.score.loan
+            || *loan.owner != LOANS_MARKETPLACE_ID
+        {
+            return Ok(false);
+        }
+
+        let data = loan.try_borrow_data()?;
+        let discriminator = &hash::hash(b"account:LoanAccount").to_bytes()[..8];
+        if data.len() <= LOAN_STATE_OFFSET || &data[..8] != discriminator {
+            return Ok(false);
+        }
+
+        let state = data[LOAN_STATE_OFFSET];
+        Ok(state == LOAN_STATE_SETTLED || state == LOAN_STATE_DEFAULTED)
+    }
+}
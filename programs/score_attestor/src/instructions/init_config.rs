@@ -1,10 +1,12 @@
-use crate::{event::ConfigInitialized, state::Config, ANCHOR_DISCRIMINATOR};
+use crate::{
+    error::ScoreAttestorError, event::ConfigInitialized, state::Config, ANCHOR_DISCRIMINATOR,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     #[account(
-        init_if_needed,
+        init,
         payer = admin,
         seeds = [b"score_config"],
         bump,
@@ -23,15 +25,28 @@ impl<'info> InitializeConfig<'info> {
         &mut self,
         bump: u8,
         attestor: Pubkey,
-        secp256k1_pubkey: [u8; 65],
+        oracle_threshold: u8,
+        max_validity_secs: i64,
+        max_staleness_secs: i64,
     ) -> Result<()> {
+        require!(attestor != Pubkey::default(), ScoreAttestorError::InvalidParam);
+        require!(oracle_threshold > 0, ScoreAttestorError::InvalidOracleThreshold);
+        require!(max_validity_secs > 0, ScoreAttestorError::InvalidExpiry);
+        require!(max_staleness_secs > 0, ScoreAttestorError::InvalidMaxStaleness);
+
         let cfg = &mut self.config;
 
         cfg.admin = self.admin.key();
+        cfg.pending_admin = None;
         cfg.bump = bump;
         cfg.paused = false;
         cfg.attestor = attestor;
-        cfg.secp256k1_pubkey = secp256k1_pubkey;
+        // Oracles are added afterwards; posting fails until the set can meet the threshold
+        cfg.oracle_threshold = oracle_threshold;
+        cfg.oracles = Vec::new();
+        cfg.max_validity_secs = max_validity_secs;
+        cfg.max_staleness_secs = max_staleness_secs;
+        cfg.models = Vec::new();
 
         emit!(ConfigInitialized {
             admin: cfg.admin,
@@ -0,0 +1,154 @@
+use crate::{
+    error::ScoreAttestorError,
+    event::{DisputeResolved, ScoreDisputed, ScoreRevoked},
+    instructions::post_scoreattestation::record_score,
+    state::{
+        Config, DisputeResolution, OracleSignature, ScoreAttestation, ScoreHistory, ScorePayload,
+        ScoringModel,
+    },
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DisputeScore<'info> {
+    #[account(
+        seeds = [b"score_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub subject: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"score", score.subject.as_ref(), score.scope().as_ref()],
+        bump = score.bump,
+        constraint = score.subject == subject.key()
+    )]
+    pub score: Account<'info, ScoreAttestation>,
+}
+
+impl<'info> DisputeScore<'info> {
+    pub fn dispute_score(&mut self, reason_hash: [u8; 32]) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+
+        let score = &mut self.score;
+        require!(!score.disputed, ScoreAttestorError::ScoreDisputed);
+        // Once resolved, the same payload can't be disputed again to hold off new posts
+        require!(
+            score.nonce > score.disputed_nonce,
+            ScoreAttestorError::AlreadyDisputed
+        );
+        require!(reason_hash != [0u8; 32], ScoreAttestorError::InvalidParam);
+
+        score.disputed = true;
+        score.dispute_reason_hash = reason_hash;
+        score.disputed_at = Clock::get()?.unix_timestamp;
+        score.disputed_nonce = score.nonce;
+
+        emit!(ScoreDisputed {
+            subject: score.subject,
+            loan: score.loan,
+            reason_hash,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"score_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub resolver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"score", score.subject.as_ref(), score.scope().as_ref()],
+        bump = score.bump
+    )]
+    pub score: Account<'info, ScoreAttestation>,
+
+    // Only needed for amendments, which must come from the score's own model
+    #[account(constraint = model.key() == score.model)]
+    pub model: Option<Account<'info, ScoringModel>>,
+
+    #[account(
+        mut,
+        seeds = [b"score_history", score.subject.as_ref()],
+        bump = history.bump
+    )]
+    pub history: Option<Account<'info, ScoreHistory>>,
+}
+
+impl<'info> ResolveDispute<'info> {
+    /// `amendment`, `signatures`, `model` and `history` are only used, and then required,
+    /// for `Amend`
+    pub fn resolve_dispute(
+        &mut self,
+        resolution: DisputeResolution,
+        amendment: Option<ScorePayload>,
+        signatures: Vec<OracleSignature>,
+    ) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+
+        let resolver = self.resolver.key();
+        require!(
+            resolver == self.config.admin || resolver == self.config.attestor,
+            ScoreAttestorError::UnauthorizedResolver
+        );
+        require!(self.score.disputed, ScoreAttestorError::NoActiveDispute);
+        require!(
+            amendment.is_some() == (resolution == DisputeResolution::Amend),
+            ScoreAttestorError::InvalidParam
+        );
+
+        self.score.disputed = false;
+
+        match resolution {
+            DisputeResolution::Uphold => {}
+            DisputeResolution::Amend => {
+                let payload = amendment.ok_or(ScoreAttestorError::InvalidParam)?;
+                let (Some(model), Some(history)) = (&self.model, &mut self.history) else {
+                    return err!(ScoreAttestorError::AmendmentAccountsRequired);
+                };
+                let (subject, loan, bump) = (self.score.subject, self.score.loan, self.score.bump);
+                let history_bump = history.bump;
+
+                record_score(
+                    &self.config,
+                    model,
+                    &resolver,
+                    &mut self.score,
+                    bump,
+                    history,
+                    history_bump,
+                    subject,
+                    loan,
+                    &payload,
+                    &signatures,
+                )?;
+            }
+            DisputeResolution::Revoke => {
+                self.score.revoked = true;
+                emit!(ScoreRevoked {
+                    subject: self.score.subject,
+                    loan: self.score.loan
+                });
+            }
+        }
+
+        emit!(DisputeResolved {
+            subject: self.score.subject,
+            loan: self.score.loan,
+            resolution,
+            resolved_by: resolver,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    error::ScoreAttestorError,
+    event::{ModelAdded, ModelStatusSet},
+    state::{Config, ScoringModel},
+    ANCHOR_DISCRIMINATOR, MAX_MODELS,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(model_id: [u8; 32], version: u16)]
+pub struct AddModel<'info> {
+    #[account(
+        mut,
+        seeds = [b"score_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"model", model_id.as_ref(), version.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + ScoringModel::INIT_SPACE
+    )]
+    pub model: Account<'info, ScoringModel>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddModel<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_model(
+        &mut self,
+        bump: u8,
+        model_id: [u8; 32],
+        version: u16,
+        grade_scale: u8,
+        min_score: u16,
+        max_score: u16,
+        pd_calibration_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!self.config.paused, ScoreAttestorError::Paused);
+        // Every grade needs at least one score in its band
+        require!(
+            grade_scale > 0
+                && min_score <= max_score
+                && (max_score - min_score) as u32 + 1 >= grade_scale as u32,
+            ScoreAttestorError::InvalidParam
+        );
+
+        let model_key = self.model.key();
+        let cfg = &mut self.config;
+        require!(!cfg.is_model(&model_key), ScoreAttestorError::ModelExists);
+        require!(cfg.models.len() < MAX_MODELS, ScoreAttestorError::TooManyModels);
+
+        self.model.set_inner(ScoringModel {
+            model_id,
+            version,
+            grade_scale,
+            min_score,
+            max_score,
+            pd_calibration_hash,
+            enabled: true,
+            created_at: Clock::get()?.unix_timestamp,
+            bump,
+        });
+        cfg.models.push(model_key);
+
+        emit!(ModelAdded {
+            model: model_key,
+            model_id,
+            version,
+            grade_scale,
+            min_score,
+            max_score,
+            pd_calibration_hash,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetModelStatus<'info> {
+    #[account(
+        seeds = [b"score_config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"model", model.model_id.as_ref(), model.version.to_le_bytes().as_ref()],
+        bump = model.bump
+    )]
+    pub model: Account<'info, ScoringModel>,
+}
+
+impl<'info> SetModelStatus<'info> {
+    /// Disabling a model stops new posts; scores it already produced stay as they are
+    pub fn set_model_status(&mut self, enabled: bool) -> Result<()> {
+        require!(
+            self.config.is_model(&self.model.key()),
+            ScoreAttestorError::ModelNotFound
+        );
+
+        self.model.enabled = enabled;
+
+        emit!(ModelStatusSet {
+            model: self.model.key(),
+            model_id: self.model.model_id,
+            version: self.model.version,
+            enabled,
+        });
+
+        Ok(())
+    }
+}
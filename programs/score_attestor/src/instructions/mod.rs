@@ -1,9 +1,17 @@
 pub mod init_config;
+pub mod accept_admin;
 pub mod admin;
 pub mod admin_score;
+pub mod close_score_attestation;
+pub mod manage_dispute;
+pub mod manage_model;
 pub mod post_scoreattestation;
 
 pub use init_config::*;
+pub use accept_admin::*;
 pub use post_scoreattestation::*;
 pub use admin::*;
 pub use admin_score::*;
+pub use close_score_attestation::*;
+pub use manage_dispute::*;
+pub use manage_model::*;
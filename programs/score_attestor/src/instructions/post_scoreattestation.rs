@@ -1,15 +1,19 @@
 use crate::{
-    error::ScoreAttestorError, event::ScorePosted, state::ScoreAttestation, ANCHOR_DISCRIMINATOR,
+    error::ScoreAttestorError,
+    event::ScorePosted,
+    state::{Config, OracleSignature, ScoreAttestation, ScoreHistory, ScorePayload, ScoringModel},
+    ANCHOR_DISCRIMINATOR, MAX_ORACLES,
 };
 use anchor_lang::{prelude::*, solana_program::secp256k1_recover::secp256k1_recover};
 
+/// Loan-specific score, overrides the subject-level score for that loan
 #[derive(Accounts)]
 pub struct PostScoreAttestation<'info> {
     #[account(
         seeds = [b"score_config"],
         bump = config.bump
     )]
-    pub config: Account<'info, crate::state::Config>,
+    pub config: Account<'info, Config>,
 
     /// CHECK: subject
     pub subject: UncheckedAccount<'info>,
@@ -17,6 +21,8 @@ pub struct PostScoreAttestation<'info> {
     /// CHECK: loan
     pub loan: UncheckedAccount<'info>,
 
+    pub model: Account<'info, ScoringModel>,
+
     #[account(mut)]
     pub attestor: Signer<'info>,
 
@@ -29,6 +35,15 @@ pub struct PostScoreAttestation<'info> {
     )]
     pub score: Account<'info, ScoreAttestation>,
 
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        seeds = [b"score_history", subject.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + ScoreHistory::INIT_SPACE
+    )]
+    pub history: Account<'info, ScoreHistory>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -36,72 +51,218 @@ impl<'info> PostScoreAttestation<'info> {
     pub fn post_score_attestation(
         &mut self,
         bump: u8,
-        score: u16,
-        grade: u8,
-        pd_bps: u32,
-        recommended_min_collateral_bps: u16,
-        expiry_ts: i64,
-        message: [u8; 32],
-        signature: [u8; 64],
-        recover_id: u8,
+        history_bump: u8,
+        payload: ScorePayload,
+        signatures: Vec<OracleSignature>,
     ) -> Result<()> {
-        let now = Clock::get()?.unix_timestamp;
-        require!(expiry_ts > now, ScoreAttestorError::InvalidExpiry);
+        require!(
+            self.attestor.key() == self.config.attestor,
+            ScoreAttestorError::UnauthorizedAttestor
+        );
+
+        record_score(
+            &self.config,
+            &self.model,
+            &self.attestor.key(),
+            &mut self.score,
+            bump,
+            &mut self.history,
+            history_bump,
+            self.subject.key(),
+            self.loan.key(),
+            &payload,
+            &signatures,
+        )
+    }
+}
+
+/// Subject-level score, reusable across loans and products
+#[derive(Accounts)]
+pub struct PostSubjectScore<'info> {
+    #[account(
+        seeds = [b"score_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: subject
+    pub subject: UncheckedAccount<'info>,
+
+    pub model: Account<'info, ScoringModel>,
 
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        seeds = [b"score", subject.key().as_ref(), model.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + ScoreAttestation::INIT_SPACE
+    )]
+    pub score: Account<'info, ScoreAttestation>,
+
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        seeds = [b"score_history", subject.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + ScoreHistory::INIT_SPACE
+    )]
+    pub history: Account<'info, ScoreHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostSubjectScore<'info> {
+    pub fn post_subject_score(
+        &mut self,
+        bump: u8,
+        history_bump: u8,
+        payload: ScorePayload,
+        signatures: Vec<OracleSignature>,
+    ) -> Result<()> {
         require!(
             self.attestor.key() == self.config.attestor,
             ScoreAttestorError::UnauthorizedAttestor
         );
 
-        verify_secp256k1_sig(
-            &self.config.secp256k1_pubkey,
-            &signature,
-            recover_id,
-            &message,
-        )?;
-
-        let att = &mut self.score;
-        att.bump = bump;
-        att.subject = self.subject.key();
-        att.loan = self.loan.key();
-        att.score = score;
-        att.grade = grade;
-        att.pd_bps = pd_bps;
-        att.recommended_min_collateral_bps = recommended_min_collateral_bps;
-        att.attestor = self.config.attestor;
-        att.posted_at = now;
-        att.expiry_ts = expiry_ts;
-        att.revoked = false;
-
-        emit!(ScorePosted {
-            subject: att.subject,
-            loan: att.loan,
-            score,
-            grade,
-            pd_bps,
-            recommended_min_collateral_bps,
-            expiry_ts,
-        });
-
-        Ok(())
+        record_score(
+            &self.config,
+            &self.model,
+            &self.attestor.key(),
+            &mut self.score,
+            bump,
+            &mut self.history,
+            history_bump,
+            self.subject.key(),
+            Pubkey::default(),
+            &payload,
+            &signatures,
+        )
     }
 }
 
-fn verify_secp256k1_sig(
-    expected_pubkey: &[u8; 65],
-    signature: &[u8; 64],
-    recover_id: u8,
+/// Checks the model, nonce and oracle signatures, then overwrites `att` and appends
+/// it to the subject's history. Callers authorize the signer.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_score(
+    config: &Config,
+    model: &Account<ScoringModel>,
+    payer: &Pubkey,
+    att: &mut ScoreAttestation,
+    bump: u8,
+    history: &mut ScoreHistory,
+    history_bump: u8,
+    subject: Pubkey,
+    loan: Pubkey,
+    payload: &ScorePayload,
+    signatures: &[OracleSignature],
+) -> Result<()> {
+    require!(!config.paused, ScoreAttestorError::Paused);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        payload.expiry_ts > now && payload.expiry_ts - now <= config.max_validity_secs,
+        ScoreAttestorError::InvalidExpiry
+    );
+
+    // Disputed scores change only through `resolve_dispute`
+    require!(!att.disputed, ScoreAttestorError::ScoreDisputed);
+
+    require!(
+        config.is_model(&model.key()),
+        ScoreAttestorError::ModelNotFound
+    );
+    require!(model.enabled, ScoreAttestorError::ModelNotAllowed);
+    model.validate_payload(payload)?;
+
+    // A fresh account starts at nonce 0, so the first post needs nonce >= 1. A scope
+    // whose score was closed also has to get past the nonce it was closed at.
+    let scope = if loan == Pubkey::default() { model.key() } else { loan };
+    require!(
+        payload.nonce > att.nonce.max(history.nonce_floor(&scope)),
+        ScoreAttestorError::InvalidNonce
+    );
+
+    let message = payload.message_hash(&subject, &loan, &model.key());
+    verify_oracle_signatures(config, &message, signatures)?;
+
+    if att.payer == Pubkey::default() {
+        att.payer = *payer;
+    }
+    att.bump = bump;
+    att.subject = subject;
+    att.loan = loan;
+    att.score = payload.score;
+    att.grade = payload.grade;
+    att.pd_bps = payload.pd_bps;
+    att.recommended_min_collateral_bps = payload.recommended_min_collateral_bps;
+    att.attestor = config.attestor;
+    att.model = model.key();
+    att.model_version = model.version;
+    att.posted_at = now;
+    att.expiry_ts = payload.expiry_ts;
+    att.stale_at = now.saturating_add(config.max_staleness_secs);
+    att.revoked = false;
+    att.nonce = payload.nonce;
+
+    if history.subject == Pubkey::default() {
+        history.subject = subject;
+        history.bump = history_bump;
+    }
+    let sequence = history.record(att);
+
+    emit!(ScorePosted {
+        subject,
+        loan,
+        score: payload.score,
+        grade: payload.grade,
+        pd_bps: payload.pd_bps,
+        recommended_min_collateral_bps: payload.recommended_min_collateral_bps,
+        expiry_ts: payload.expiry_ts,
+        model: att.model,
+        model_version: att.model_version,
+        nonce: payload.nonce,
+        posted_at: now,
+        sequence,
+    });
+
+    Ok(())
+}
+
+/// Requires at least `oracle_threshold` distinct oracles to have signed `message_hash`.
+/// Signers are identified by key recovery, so signatures can come in any order.
+fn verify_oracle_signatures(
+    config: &Config,
     message_hash: &[u8; 32],
+    signatures: &[OracleSignature],
 ) -> Result<()> {
-    let recovered_pubkey = secp256k1_recover(message_hash, recover_id, signature)
-        .map_err(|_| ScoreAttestorError::InvalidSignature)?;
+    require!(
+        signatures.len() <= MAX_ORACLES,
+        ScoreAttestorError::TooManyOracles
+    );
+
+    let mut signers: Vec<[u8; 65]> = Vec::with_capacity(signatures.len());
+    for sig in signatures {
+        let recovered_pubkey = secp256k1_recover(message_hash, sig.recovery_id, &sig.signature)
+            .map_err(|_| ScoreAttestorError::InvalidSignature)?;
 
-    let mut full_pubkey = vec![4u8];
-    full_pubkey.extend_from_slice(&recovered_pubkey.to_bytes());
+        let mut full_pubkey = [4u8; 65];
+        full_pubkey[1..].copy_from_slice(&recovered_pubkey.to_bytes());
+
+        require!(
+            config.is_oracle(&full_pubkey),
+            ScoreAttestorError::UnauthorizedOracle
+        );
+        if !signers.contains(&full_pubkey) {
+            signers.push(full_pubkey);
+        }
+    }
 
     require!(
-        full_pubkey == expected_pubkey,
-        ScoreAttestorError::InvalidSignature
+        signers.len() >= config.oracle_threshold as usize,
+        ScoreAttestorError::InsufficientOracleSigners
     );
 
     Ok(())
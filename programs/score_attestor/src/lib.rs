@@ -1,4 +1,4 @@
-#![allow(unexpected_cfgs, deprecated)]
+#![allow(unexpected_cfgs, deprecated, clippy::too_many_arguments)]
 pub mod constants;
 pub mod error;
 pub mod event;
@@ -20,14 +20,26 @@ pub mod score_attestor {
     pub fn initialize_config(
         ctx: Context<InitializeConfig>,
         attestor: Pubkey,
-        secp256k1_pubkey: [u8; 65],
+        oracle_threshold: u8,
+        max_validity_secs: i64,
+        max_staleness_secs: i64,
     ) -> Result<()> {
         let bump = ctx.bumps.config;
-        ctx.accounts.init_config(bump, attestor, secp256k1_pubkey)
+        ctx.accounts.init_config(
+            bump,
+            attestor,
+            oracle_threshold,
+            max_validity_secs,
+            max_staleness_secs,
+        )
+    }
+
+    pub fn propose_admin(ctx: Context<AdminOnly>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
     }
 
-    pub fn set_admin(ctx: Context<AdminOnly>, new_admin: Pubkey) -> Result<()> {
-        ctx.accounts.set_admin(new_admin)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
     }
 
     pub fn set_paused(ctx: Context<AdminOnly>, paused: bool) -> Result<()> {
@@ -37,8 +49,51 @@ pub mod score_attestor {
         ctx.accounts.set_issuer(issuer)
     }
 
-    pub fn set_secp256k1_pubkey(ctx: Context<AdminOnly>, secp256k1_pubkey: [u8; 65]) -> Result<()> {
-        ctx.accounts.set_secp256k1_pubkey(secp256k1_pubkey)
+    pub fn add_oracle(ctx: Context<AdminOnly>, oracle: [u8; 65]) -> Result<()> {
+        ctx.accounts.add_oracle(oracle)
+    }
+
+    pub fn remove_oracle(ctx: Context<AdminOnly>, oracle: [u8; 65]) -> Result<()> {
+        ctx.accounts.remove_oracle(oracle)
+    }
+
+    pub fn set_oracle_threshold(ctx: Context<AdminOnly>, oracle_threshold: u8) -> Result<()> {
+        ctx.accounts.set_oracle_threshold(oracle_threshold)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_model(
+        ctx: Context<AddModel>,
+        model_id: [u8; 32],
+        version: u16,
+        grade_scale: u8,
+        min_score: u16,
+        max_score: u16,
+        pd_calibration_hash: [u8; 32],
+    ) -> Result<()> {
+        let bump = ctx.bumps.model;
+        ctx.accounts.add_model(
+            bump,
+            model_id,
+            version,
+            grade_scale,
+            min_score,
+            max_score,
+            pd_calibration_hash,
+        )
+    }
+
+    pub fn set_model_status(ctx: Context<SetModelStatus>, enabled: bool) -> Result<()> {
+        ctx.accounts.set_model_status(enabled)
+    }
+
+    pub fn set_score_windows(
+        ctx: Context<AdminOnly>,
+        max_validity_secs: i64,
+        max_staleness_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_score_windows(max_validity_secs, max_staleness_secs)
     }
 
     pub fn revoke_attestation(ctx: Context<AdminAndScore>) -> Result<()> {
@@ -52,6 +107,25 @@ pub mod score_attestor {
         ctx.accounts.update_attestation_expiry(new_expiry_ts)
     }
 
+    pub fn dispute_score(ctx: Context<DisputeScore>, reason_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.dispute_score(reason_hash)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        resolution: DisputeResolution,
+        amendment: Option<ScorePayload>,
+        signatures: Vec<OracleSignature>,
+    ) -> Result<()> {
+        ctx.accounts
+            .resolve_dispute(resolution, amendment, signatures)
+    }
+
+    pub fn close_score_attestation(ctx: Context<CloseScoreAttestation>) -> Result<()> {
+        ctx.accounts.close_score_attestation()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn post_score_attestation(
         ctx: Context<PostScoreAttestation>,
         score: u16,
@@ -59,22 +133,47 @@ pub mod score_attestor {
         pd_bps: u32,
         recommended_min_collateral_bps: u16,
         expiry_ts: i64,
-        message: [u8; 32],
-        signature: [u8; 64],
-        recover_id: u8,
+        nonce: u64,
+        signatures: Vec<OracleSignature>,
     ) -> Result<()> {
         let bump = ctx.bumps.score;
+        let history_bump = ctx.bumps.history;
+        let payload = ScorePayload {
+            score,
+            grade,
+            pd_bps,
+            recommended_min_collateral_bps,
+            expiry_ts,
+            nonce,
+        };
 
-        ctx.accounts.post_score_attestation(
-            bump,
+        ctx.accounts
+            .post_score_attestation(bump, history_bump, payload, signatures)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn post_subject_score(
+        ctx: Context<PostSubjectScore>,
+        score: u16,
+        grade: u8,
+        pd_bps: u32,
+        recommended_min_collateral_bps: u16,
+        expiry_ts: i64,
+        nonce: u64,
+        signatures: Vec<OracleSignature>,
+    ) -> Result<()> {
+        let bump = ctx.bumps.score;
+        let history_bump = ctx.bumps.history;
+        let payload = ScorePayload {
             score,
             grade,
             pd_bps,
             recommended_min_collateral_bps,
             expiry_ts,
-            message,
-            signature,
-            recover_id,
-        )
+            nonce,
+        };
+
+        ctx.accounts
+            .post_subject_score(bump, history_bump, payload, signatures)
     }
 }
@@ -1,11 +1,34 @@
 use anchor_lang::prelude::*;
 
+use crate::{MAX_MODELS, MAX_ORACLES};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
     pub admin: Pubkey,                 // 32
+    pub pending_admin: Option<Pubkey>, // 1 + 32: proposed admin, takes over once it accepts
     pub bump: u8,                      // 1
     pub paused: bool,                  // 1
     pub attestor: Pubkey,              // 32
-    pub secp256k1_pubkey: [u8; 65],   // 65
-}
\ No newline at end of file
+    pub oracle_threshold: u8,          // 1: signatures required per score (M of N)
+    pub max_validity_secs: i64,        // 8: furthest a new expiry may be from now
+    pub max_staleness_secs: i64,       // 8: age after which a score stops being fresh
+
+    // Uncompressed secp256k1 keys of the scoring oracles
+    #[max_len(MAX_ORACLES)]
+    pub oracles: Vec<[u8; 65]>,        // 4 + 65 * MAX_ORACLES
+
+    // Registered `ScoringModel` PDAs, one entry per model version
+    #[max_len(MAX_MODELS)]
+    pub models: Vec<Pubkey>,           // 4 + 32 * MAX_MODELS
+}
+
+impl Config {
+    pub fn is_oracle(&self, key: &[u8; 65]) -> bool {
+        self.oracles.iter().any(|o| o == key)
+    }
+
+    pub fn is_model(&self, model: &Pubkey) -> bool {
+        self.models.contains(model)
+    }
+}
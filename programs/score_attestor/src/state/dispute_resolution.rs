@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeResolution {
+    Uphold, // Score stands as posted
+    Amend,  // Replaced by a newly signed payload
+    Revoke, // Score withdrawn
+}
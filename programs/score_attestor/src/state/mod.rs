@@ -1,5 +1,15 @@
 pub mod scoreattestation;
 pub mod config;
+pub mod dispute_resolution;
+pub mod oracle_signature;
+pub mod score_history;
+pub mod score_payload;
+pub mod scoring_model;
 
 pub use scoreattestation::*;
 pub use config::*;
+pub use dispute_resolution::*;
+pub use oracle_signature::*;
+pub use score_history::*;
+pub use score_payload::*;
+pub use scoring_model::*;
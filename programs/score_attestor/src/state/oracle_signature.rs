@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+/// One oracle's secp256k1 signature over a score payload
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleSignature {
+    pub signature: [u8; 64],
+    pub recovery_id: u8,
+}
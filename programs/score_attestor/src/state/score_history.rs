@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{ScoreAttestation, MAX_NONCE_FLOORS, MAX_SCORE_HISTORY};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ScoreHistoryEntry {
+    pub loan: Pubkey, // Default for subject-level scores
+    pub score: u16,
+    pub grade: u8,
+    pub pd_bps: u32,
+    pub model: Pubkey,
+    pub posted_at: i64,
+}
+
+/// Last nonce posted under a score scope whose `ScoreAttestation` has been closed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct NonceFloor {
+    pub scope: Pubkey,
+    pub nonce: u64,
+}
+
+/// Ring buffer of the last `MAX_SCORE_HISTORY` scores posted for a subject.
+/// Older entries are only recoverable from `ScorePosted` events.
+#[account]
+#[derive(InitSpace)]
+pub struct ScoreHistory {
+    pub subject: Pubkey,
+    pub total_posted: u64, // Postings ever recorded, also the sequence of the next one
+    pub head: u16,         // Slot the next entry is written to once the buffer is full
+    pub bump: u8,
+
+    #[max_len(MAX_SCORE_HISTORY)]
+    pub entries: Vec<ScoreHistoryEntry>,
+
+    // Closing a score resets its nonce, so the floor is kept here to stop old
+    // payloads being replayed into a re-created account
+    #[max_len(MAX_NONCE_FLOORS)]
+    pub nonce_floors: Vec<NonceFloor>,
+    pub evicted_nonce_floor: u64, // Highest floor pushed out of `nonce_floors`; applies to every scope
+}
+
+impl ScoreHistory {
+    /// Records a freshly posted score, evicting the oldest entry when full.
+    /// Returns the posting's sequence number.
+    pub fn record(&mut self, score: &ScoreAttestation) -> u64 {
+        let entry = ScoreHistoryEntry {
+            loan: score.loan,
+            score: score.score,
+            grade: score.grade,
+            pd_bps: score.pd_bps,
+            model: score.model,
+            posted_at: score.posted_at,
+        };
+
+        if self.entries.len() < MAX_SCORE_HISTORY {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head as usize] = entry;
+            self.head = ((self.head as usize + 1) % MAX_SCORE_HISTORY) as u16;
+        }
+
+        let sequence = self.total_posted;
+        self.total_posted += 1;
+        sequence
+    }
+
+    /// Nonce a payload for `scope` has to exceed, on top of the live account's own nonce
+    pub fn nonce_floor(&self, scope: &Pubkey) -> u64 {
+        self.nonce_floors
+            .iter()
+            .find(|f| f.scope == *scope)
+            .map_or(0, |f| f.nonce)
+            .max(self.evicted_nonce_floor)
+    }
+
+    /// Remembers the nonce of a score being closed. When full, the oldest floor is
+    /// folded into `evicted_nonce_floor`, which only ever makes the check stricter.
+    pub fn raise_nonce_floor(&mut self, scope: Pubkey, nonce: u64) {
+        if let Some(floor) = self.nonce_floors.iter_mut().find(|f| f.scope == scope) {
+            floor.nonce = floor.nonce.max(nonce);
+            return;
+        }
+
+        if self.nonce_floors.len() == MAX_NONCE_FLOORS {
+            let oldest = self.nonce_floors.remove(0);
+            self.evicted_nonce_floor = self.evicted_nonce_floor.max(oldest.nonce);
+        }
+        self.nonce_floors.push(NonceFloor { scope, nonce });
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+/// Score fields covered by the oracle signatures
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ScorePayload {
+    pub score: u16,
+    pub grade: u8,
+    pub pd_bps: u32,
+    pub recommended_min_collateral_bps: u16,
+    pub expiry_ts: i64,
+    pub nonce: u64,
+}
+
+impl ScorePayload {
+    /// Hash the oracles sign: keccak256 over the program id, subject, loan, score fields,
+    /// expiry, model and nonce, with integers little-endian. Subject-level scores use the
+    /// default pubkey as loan.
+    ///
+    /// The nonce has to exceed the last one posted under the same score scope (loan, or
+    /// model for subject-level scores), including scores closed since. Only the last
+    /// `MAX_NONCE_FLOORS` closed scopes per subject are tracked individually; older ones
+    /// are folded into `ScoreHistory::evicted_nonce_floor`, which every scope of that
+    /// subject must then exceed. Oracles that keep one monotonic nonce per subject,
+    /// rather than per scope, never run into that floor.
+    pub fn message_hash(&self, subject: &Pubkey, loan: &Pubkey, model: &Pubkey) -> [u8; 32] {
+        keccak::hashv(&[
+            crate::ID.as_ref(),
+            subject.as_ref(),
+            loan.as_ref(),
+            &self.score.to_le_bytes(),
+            &[self.grade],
+            &self.pd_bps.to_le_bytes(),
+            &self.recommended_min_collateral_bps.to_le_bytes(),
+            &self.expiry_ts.to_le_bytes(),
+            model.as_ref(),
+            &self.nonce.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
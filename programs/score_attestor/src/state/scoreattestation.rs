@@ -4,14 +4,45 @@ use anchor_lang::prelude::*;
 #[derive(InitSpace)]
 pub struct ScoreAttestation {
     pub subject: Pubkey,                     // 32: the user wallet or identity
-    pub loan: Pubkey,                        // 32: the loan account
+    pub loan: Pubkey,                        // 32: the loan account, default for subject-level scores
     pub score: u16,                          // 2: minimal score
     pub grade: u8,                            // 1: grade of the score
     pub pd_bps: u32,                          // 4: probability of default in basis points
     pub recommended_min_collateral_bps: u16, // 2: recommended collateral in basis points
     pub attestor: Pubkey,                     // 32: attestor / issuer public key
+    pub payer: Pubkey,                        // 32: paid the rent, refunded on close
+    pub model: Pubkey,                        // 32: ScoringModel PDA that produced the score
+    pub model_version: u16,                   // 2: version of that model
     pub posted_at: i64,                       // 8: timestamp when score was posted
     pub expiry_ts: i64,                       // 8: expiry timestamp for this score
+    pub stale_at: i64,                        // 8: posted_at + max_staleness_secs at posting time
     pub revoked: bool,                        // 1: whether the score is revoked
+    pub nonce: u64,                           // 8: nonce of the last signed payload, must increase
+    pub disputed: bool,                       // 1: flagged by the subject, consumers must not use it
+    pub dispute_reason_hash: [u8; 32],        // 32: hash of the subject's off-chain complaint
+    pub disputed_at: i64,                     // 8
+    pub disputed_nonce: u64,                  // 8: nonce of the last disputed payload, each can be disputed once
     pub bump: u8,                             // 1: PDA bump
 }
+
+impl ScoreAttestation {
+    /// Subject-level scores are seeded by model instead of loan
+    pub fn is_subject_level(&self) -> bool {
+        self.loan == Pubkey::default()
+    }
+
+    /// Second PDA seed: the loan, or the model for subject-level scores
+    pub fn scope(&self) -> Pubkey {
+        if self.is_subject_level() {
+            self.model
+        } else {
+            self.loan
+        }
+    }
+
+    /// Not revoked, not expired and not older than the staleness window.
+    /// Consumers must also reject `disputed` scores.
+    pub fn is_fresh(&self, now: i64) -> bool {
+        !self.revoked && now < self.expiry_ts && now < self.stale_at
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ScoreAttestorError, ScorePayload, MAX_COLLATERAL_BPS, MAX_PD_BPS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct ScoringModel {
+    pub model_id: [u8; 32],             // 32: identifier shared by all versions of a model
+    pub version: u16,                   // 2: model version, part of the PDA seeds
+    pub grade_scale: u8,                // 1: number of score bands, valid grades are 0..grade_scale
+    pub min_score: u16,                 // 2: lowest score the model can produce
+    pub max_score: u16,                 // 2: highest score the model can produce
+    pub pd_calibration_hash: [u8; 32],  // 32: hash of the off-chain PD calibration table
+    pub enabled: bool,                  // 1: disabled models can't back new scores
+    pub created_at: i64,                // 8
+    pub bump: u8,                       // 1
+}
+
+impl ScoringModel {
+    /// Grade for `score`: the score range is split into `grade_scale` equal bands,
+    /// grade 0 being the lowest band
+    pub fn grade_for(&self, score: u16) -> u8 {
+        let width = (self.max_score - self.min_score) as u32 + 1;
+        ((score - self.min_score) as u32 * self.grade_scale as u32 / width) as u8
+    }
+
+    pub fn validate_payload(&self, payload: &ScorePayload) -> Result<()> {
+        require!(
+            payload.score >= self.min_score && payload.score <= self.max_score,
+            ScoreAttestorError::InvalidParam
+        );
+        require!(
+            payload.grade == self.grade_for(payload.score),
+            ScoreAttestorError::InvalidParam
+        );
+        require!(payload.pd_bps <= MAX_PD_BPS, ScoreAttestorError::InvalidParam);
+        require!(
+            payload.recommended_min_collateral_bps <= MAX_COLLATERAL_BPS,
+            ScoreAttestorError::InvalidParam
+        );
+        Ok(())
+    }
+}
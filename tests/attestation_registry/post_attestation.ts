@@ -3,6 +3,15 @@ import { Program } from "@coral-xyz/anchor";
 import { AttestationRegistry } from "../../target/types/attestation_registry";
 import { PublicKey } from "@solana/web3.js";
 import { BN } from "bn.js";
+import {
+    createMint,
+    createAssociatedTokenAccount,
+    getAssociatedTokenAddressSync,
+    mintTo,
+    getAccount,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    TOKEN_PROGRAM_ID
+} from "@solana/spl-token";
 import { expect } from "chai";
 
 import { Buffer } from "buffer"
@@ -48,6 +57,116 @@ describe("attestation_registry", () => {
     let configBump: number;
     let zkPassIssuerPubkey: PublicKey;
     let plaidIssuerPubkey: PublicKey;
+    let zkPassSigner: { privateKey: Uint8Array; publicKey: Uint8Array };
+
+    const ZK_PASS_SCHEMA_ID = 1;
+    const PLAID_INCOME_SCHEMA_ID = 4;
+
+    function schemaIdSeed(schemaId: number): Buffer {
+        const idBytes = Buffer.alloc(2);
+        idBytes.writeUInt16LE(schemaId);
+        return idBytes;
+    }
+
+    function issuerKeyPda(issuer: PublicKey, keyId: number): PublicKey {
+        const idBytes = Buffer.alloc(2);
+        idBytes.writeUInt16LE(keyId);
+        return anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("issuer_key"), issuer.toBuffer(), idBytes],
+            program.programId
+        )[0];
+    }
+
+    async function addSecp256k1Key(issuer: PublicKey, keyId: number, publicKey: Uint8Array) {
+        await program.methods
+            .addIssuerKey(issuer, keyId, { secp256k1: {} }, Array.from(publicKey), new BN(0), new BN(0))
+            .accountsStrict({
+                config: configPda,
+                signingKey: issuerKeyPda(issuer, keyId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+    }
+
+    function newSecp256k1Key(): { privateKey: Uint8Array; publicKey: Uint8Array } {
+        let privateKey: Uint8Array;
+        do {
+            privateKey = anchor.web3.Keypair.generate().secretKey.slice(0, 32);
+        } while (!secp256k1.privateKeyVerify(privateKey));
+        return { privateKey, publicKey: secp256k1.publicKeyCreate(privateKey, false) };
+    }
+
+    // Mirrors `PostAttestation::message_hash`
+    function postMessageHash(
+        subject: PublicKey,
+        schemaId: number,
+        issuer: PublicKey,
+        claimHash: Uint8Array,
+        expiryTs: number,
+        data: Buffer,
+        version: number
+    ): Buffer {
+        const expiry = Buffer.alloc(8);
+        expiry.writeBigInt64LE(BigInt(expiryTs));
+        const versionBytes = Buffer.alloc(4);
+        versionBytes.writeUInt32LE(version);
+        return Buffer.from(sha3.keccak_256.digest(Buffer.concat([
+            program.programId.toBuffer(),
+            subject.toBuffer(),
+            schemaIdSeed(schemaId),
+            issuer.toBuffer(),
+            Buffer.from(claimHash),
+            expiry,
+            Buffer.from(sha3.keccak_256.digest(data)),
+            versionBytes,
+        ])));
+    }
+
+    function signPost(privateKey: Uint8Array, ...fields: Parameters<typeof postMessageHash>) {
+        const { signature, recid } = secp256k1.ecdsaSign(postMessageHash(...fields), privateKey);
+        return { signature: Array.from(signature), recid };
+    }
+
+    function attestationPdaOf(subject: PublicKey, schemaId: number, issuer: PublicKey): PublicKey {
+        return anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("attest"), subject.toBuffer(), schemaIdSeed(schemaId), issuer.toBuffer()],
+            program.programId
+        )[0];
+    }
+
+    function tombstonePdaOf(attestation: PublicKey): PublicKey {
+        return anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("attest_tombstone"), attestation.toBuffer()],
+            program.programId
+        )[0];
+    }
+
+    // Registers an Ethereum-style issuer for `schemaId` with a fresh secp256k1 key under id 0
+    async function registerSecp256k1Issuer(schemaId: number) {
+        const issuer = anchor.web3.Keypair.generate().publicKey;
+        const signer = newSecp256k1Key();
+        await program.methods
+            .addIssuer(issuer, { ethereum: {} })
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        await program.methods
+            .allowIssuerSchema(issuer, schemaId)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        await addSecp256k1Key(issuer, 0, signer.publicKey);
+        return { issuer, signer };
+    }
+
+    function schemaPda(schemaId: number): PublicKey {
+        return anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("schema"), schemaIdSeed(schemaId)],
+            program.programId
+        )[0];
+    }
 
     async function airdrop(pubkey: PublicKey, sol = 2) {
         const sig = await provider.connection.requestAirdrop(pubkey, sol * anchor.web3.LAMPORTS_PER_SOL);
@@ -75,6 +194,22 @@ describe("attestation_registry", () => {
             .signers([admin])
             .rpc();
 
+        for (const [schemaId, name] of [
+            [ZK_PASS_SCHEMA_ID, "zkPassIdentity"],
+            [PLAID_INCOME_SCHEMA_ID, "plaidIncome"],
+        ] as [number, string][]) {
+            await program.methods
+                .addSchema(schemaId, name, 1, Array(32).fill(0), new anchor.BN(1800), true, false, 64, { ethereum: {} })
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(schemaId),
+                    admin: admin.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([admin])
+                .rpc();
+        }
+
         const allocatorAddress = "0x19a567b3b212a5b35bA0E3B600FbEd5c2eE9083d";
         const ethAddressBytes1 = Buffer.from(allocatorAddress.slice(2), "hex");
         const padded1 = Buffer.concat([Buffer.alloc(12), ethAddressBytes1]); // 32 bytes
@@ -102,6 +237,87 @@ describe("attestation_registry", () => {
             })
             .signers([admin])
             .rpc();
+
+        await program.methods
+            .allowIssuerSchema(zkPassIssuerPubkey, ZK_PASS_SCHEMA_ID)
+            .accounts({
+                config: configPda,
+                admin: admin.publicKey,
+            })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .allowIssuerSchema(plaidIssuerPubkey, PLAID_INCOME_SCHEMA_ID)
+            .accounts({
+                config: configPda,
+                admin: admin.publicKey,
+            })
+            .signers([admin])
+            .rpc();
+    });
+
+    it("Tracks per-issuer schema permissions", async () => {
+        const config = await program.account.config.fetch(configPda);
+        const zkPass = config.issuers.find((i: any) => i.pubkey.equals(zkPassIssuerPubkey));
+        const plaid = config.issuers.find((i: any) => i.pubkey.equals(plaidIssuerPubkey));
+        expect(zkPass.allowedSchemas).to.deep.equal([ZK_PASS_SCHEMA_ID]);
+        expect(plaid.allowedSchemas).to.deep.equal([PLAID_INCOME_SCHEMA_ID]);
+
+        try {
+            await program.methods
+                .allowIssuerSchema(zkPassIssuerPubkey, ZK_PASS_SCHEMA_ID)
+                .accounts({
+                    config: configPda,
+                    admin: admin.publicKey,
+                })
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("IssuerSchemaAlreadyAllowed");
+        }
+    });
+
+    it("Deprecates a schema", async () => {
+        const schemaId = 200;
+        await program.methods
+            .addSchema(schemaId, "legacyKyc", 1, Array(32).fill(0), new anchor.BN(600), false, false, 0, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(schemaId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .deprecateSchema(schemaId)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(schemaId),
+                admin: admin.publicKey,
+            })
+            .signers([admin])
+            .rpc();
+
+        const schema = await program.account.schemaDefinition.fetch(schemaPda(schemaId));
+        expect(schema.deprecated).to.eq(true);
+
+        await program.methods
+            .removeSchema(schemaId)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(schemaId),
+                admin: admin.publicKey,
+            })
+            .signers([admin])
+            .rpc();
+
+        const config = await program.account.config.fetch(configPda);
+        expect(config.schemas).to.not.include(schemaId);
+        expect(await provider.connection.getAccountInfo(schemaPda(schemaId))).to.be.null;
     });
 
     it("Posts an attestation zkPass", async () => {
@@ -113,11 +329,6 @@ describe("attestation_registry", () => {
         const taskId = "9ef83f6165d0401c815b1a892b5ac93c";
         const validatorAddress = "e504ad91fbaad88362941a65b1c4c1e1cdd5cf69e27a3a08c8f51145c2e12c6a";
         const schema = "2a5bd6de00794f71aab7a556e0bcef43";
-        const allocatorSignature = "0x45adc69669fadba9546c984179c446aac25c2c7fe5176d7e4d45138e595d7f00094dcbf015023d8c0b8da88479d10aa15a21dc4535fce9fa428a0e9293c1197e00"
-
-        const sig_bytes = hexToBytes(allocatorSignature.slice(2));
-        const signatureBytes = sig_bytes.slice(0, 64);
-        const recoverId = Array.from(sig_bytes.slice(64))[0];
         const SolanaTaskSchema = new Map([
             [SolanaTaskStruct, {
                 kind: "struct",
@@ -135,52 +346,51 @@ describe("attestation_registry", () => {
         });
         const plaintext = borsh.serialize(SolanaTaskSchema, message);
         const plaintextHash = Buffer.from(sha3.keccak_256.digest(Buffer.from(plaintext)));
-        const pubKey = secp256k1.ecdsaRecover(signatureBytes, recoverId, plaintextHash, false);
-
-        // const pubkeyHash = sha3.keccak_256.create();
-        // pubkeyHash.update(pubKey.slice(1));
-        // const hashed = Buffer.from(pubkeyHash.digest());
-        // const recoveredAddressBytes = hashed.slice(-20);
-        // const recoveredAddress = recoveredAddressBytes.toString("hex").toLowerCase();
-        // const allocatorFromProof = allocatorAddress.replace(/^0x/, "").toLowerCase();
-        // console.log("Recovered Address:", recoveredAddress);
-        // console.log("Proof Allocator Address:", allocatorFromProof);
 
         const subject = anchor.web3.Keypair.generate();
         await airdrop(subject.publicKey);
 
         const now = Math.floor(Date.now() / 1000);
         const expiryTs = now + 3500; // < 3600, safe
-        const schemaIdIndex = 1;
-        const [attestationPda] = anchor.web3.PublicKey.findProgramAddressSync(
-            [
-                Buffer.from("attest"),
-                subject.publicKey.toBuffer(),
-                Buffer.from([schemaIdIndex]),
-                zkPassIssuerPubkey.toBuffer(),
-            ],
-            program.programId
+        const attestationPda = attestationPdaOf(subject.publicKey, ZK_PASS_SCHEMA_ID, zkPassIssuerPubkey);
+
+        // The allocator's registry key signs the claim commitment for this subject
+        zkPassSigner = newSecp256k1Key();
+        await addSecp256k1Key(zkPassIssuerPubkey, 0, zkPassSigner.publicKey);
+
+        const { signature, recid } = signPost(
+            zkPassSigner.privateKey,
+            subject.publicKey,
+            ZK_PASS_SCHEMA_ID,
+            zkPassIssuerPubkey,
+            plaintextHash,
+            expiryTs,
+            Buffer.alloc(0),
+            1
         );
+        const postAccounts = {
+            config: configPda,
+            schema: schemaPda(ZK_PASS_SCHEMA_ID),
+            subject: subject.publicKey,
+            attestation: attestationPda,
+            tombstone: tombstonePdaOf(attestationPda),
+            issuer: zkPassIssuerPubkey,
+            signingKey: issuerKeyPda(zkPassIssuerPubkey, 0),
+            payer: subject.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        };
 
-        await airdrop(subject.publicKey)
         await program.methods
             .postAttestation(
-                { zkPassIdentity: {} },
+                ZK_PASS_SCHEMA_ID,
                 Array.from(plaintextHash),
+                Buffer.alloc(0),
                 new BN(expiryTs),
-                Array.from(signatureBytes),
-                recoverId,
-                Array.from(pubKey),
-                255
+                signature,
+                recid
             )
-            .accountsStrict({
-                config: configPda,
-                subject: subject.publicKey,
-                attestation: attestationPda,
-                issuer: zkPassIssuerPubkey,
-                payer: subject.publicKey,
-                systemProgram: anchor.web3.SystemProgram.programId,
-            })
+            .accountsStrict(postAccounts)
             .signers([subject])
             .rpc();
 
@@ -190,6 +400,101 @@ describe("attestation_registry", () => {
         expect(Buffer.from(attestationAccount.claimHash).toString("hex")).to.equal(
             Buffer.from(plaintextHash).toString("hex")
         );
+
+        const verification = await program.methods
+            .verifyAttestation(ZK_PASS_SCHEMA_ID)
+            .accountsStrict({
+                config: configPda,
+                subject: subject.publicKey,
+                attestation: attestationPda,
+                signingKey: issuerKeyPda(zkPassIssuerPubkey, 0),
+            })
+            .view();
+        expect(verification.status).to.deep.equal({ valid: {} });
+        expect(verification.issuer.toBase58()).to.equal(zkPassIssuerPubkey.toBase58());
+
+        await program.methods
+            .revokeMyAttestation()
+            .accountsStrict({
+                config: configPda,
+                subject: subject.publicKey,
+                attestation: attestationPda,
+            })
+            .signers([subject])
+            .rpc();
+
+        // The signature was for version 1, so it can't bring the revoked attestation back
+        try {
+            await program.methods
+                .repostAttestation(
+                    ZK_PASS_SCHEMA_ID,
+                    Array.from(plaintextHash),
+                    Buffer.alloc(0),
+                    new BN(expiryTs),
+                    signature,
+                    recid
+                )
+                .accountsStrict({ ...postAccounts, history: null })
+                .signers([subject])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidSignature");
+        }
+
+        const revoked = await program.account.attestation.fetch(attestationPda);
+        expect(revoked.revoked).to.equal(true);
+        expect(revoked.version).to.equal(1);
+
+        // Closing doesn't reset the version either, so the old signature stays dead
+        await program.methods
+            .closeAttestation()
+            .accountsStrict({
+                config: configPda,
+                subject: subject.publicKey,
+                attestation: attestationPda,
+                schema: schemaPda(ZK_PASS_SCHEMA_ID),
+                history: null,
+            })
+            .signers([subject])
+            .rpc();
+        expect(await provider.connection.getAccountInfo(attestationPda)).to.be.null;
+
+        const postWith = (sig: { signature: number[]; recid: number }) =>
+            program.methods
+                .postAttestation(
+                    ZK_PASS_SCHEMA_ID,
+                    Array.from(plaintextHash),
+                    Buffer.alloc(0),
+                    new BN(expiryTs),
+                    sig.signature,
+                    sig.recid
+                )
+                .accountsStrict(postAccounts)
+                .signers([subject])
+                .rpc();
+        try {
+            await postWith({ signature, recid });
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidSignature");
+        }
+
+        // The issuer has to sign for the version after the closed one
+        await postWith(signPost(
+            zkPassSigner.privateKey,
+            subject.publicKey,
+            ZK_PASS_SCHEMA_ID,
+            zkPassIssuerPubkey,
+            plaintextHash,
+            expiryTs,
+            Buffer.alloc(0),
+            2
+        ));
+        const reposted = await program.account.attestation.fetch(attestationPda);
+        expect(reposted.revoked).to.equal(false);
+        expect(reposted.version).to.equal(2);
+        expect((await program.account.attestationTombstone.fetch(tombstonePdaOf(attestationPda))).lastVersion).to.equal(2);
     });
 
     it("Posts an attestation Reclaim", async () => {
@@ -220,10 +525,6 @@ describe("attestation_registry", () => {
             publicData: null,
         };
 
-        const sigBytes = hexToBytes(reclaimProof.signatures[0].slice(2));
-        const signatureBytes = sigBytes.slice(0, 64);
-        let recoverId = sigBytes[64];
-        if (recoverId >= 27) recoverId -= 27;
         const ReclaimTaskSchema = new Map([
             [
                 ReclaimTaskStruct,
@@ -247,40 +548,45 @@ describe("attestation_registry", () => {
         const plaintext = borsh.serialize(ReclaimTaskSchema, message);
         const plaintextHash = Buffer.from(sha3.keccak_256.digest(Buffer.from(plaintext)));
 
-        const pubKey = secp256k1.ecdsaRecover(signatureBytes, recoverId, plaintextHash, false);
+        const reclaimSigner = newSecp256k1Key();
+        await addSecp256k1Key(plaidIssuerPubkey, 0, reclaimSigner.publicKey);
 
         const subject = anchor.web3.Keypair.generate();
         await airdrop(subject.publicKey);
 
         const now = Math.floor(Date.now() / 1000);
         const expiryTs = now + 3500;
-        const schemaIdIndex = 4;
-        const [attestationPda] = anchor.web3.PublicKey.findProgramAddressSync(
-            [
-                Buffer.from("attest"),
-                subject.publicKey.toBuffer(),
-                Buffer.from([schemaIdIndex]),
-                plaidIssuerPubkey.toBuffer(),
-            ],
-            program.programId
+        const attestationPda = attestationPdaOf(subject.publicKey, PLAID_INCOME_SCHEMA_ID, plaidIssuerPubkey);
+        const { signature, recid } = signPost(
+            reclaimSigner.privateKey,
+            subject.publicKey,
+            PLAID_INCOME_SCHEMA_ID,
+            plaidIssuerPubkey,
+            plaintextHash,
+            expiryTs,
+            Buffer.alloc(0),
+            1
         );
 
         await program.methods
             .postAttestation(
-                { plaidIncome: {} },
+                PLAID_INCOME_SCHEMA_ID,
                 Array.from(plaintextHash),
+                Buffer.alloc(0),
                 new BN(expiryTs),
-                Array.from(signatureBytes),
-                recoverId,
-                Array.from(pubKey),
-                255
+                signature,
+                recid
             )
             .accountsStrict({
                 config: configPda,
+                schema: schemaPda(PLAID_INCOME_SCHEMA_ID),
                 subject: subject.publicKey,
                 attestation: attestationPda,
+                tombstone: tombstonePdaOf(attestationPda),
                 issuer: plaidIssuerPubkey,
+                signingKey: issuerKeyPda(plaidIssuerPubkey, 0),
                 payer: subject.publicKey,
+                instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([subject])
@@ -292,5 +598,1424 @@ describe("attestation_registry", () => {
             Buffer.from(plaintextHash).toString("hex")
         );
     });
-});
 
+    it("Rotates and retires issuer signing keys", async () => {
+        const expiryTs = Math.floor(Date.now() / 1000) + 1800;
+        const claimHash = Buffer.alloc(32, 3);
+
+        const post = async (subject: anchor.web3.Keypair, keyId: number, privateKey: Uint8Array) => {
+            const attestation = attestationPdaOf(subject.publicKey, ZK_PASS_SCHEMA_ID, zkPassIssuerPubkey);
+            const { signature, recid } = signPost(
+                privateKey,
+                subject.publicKey,
+                ZK_PASS_SCHEMA_ID,
+                zkPassIssuerPubkey,
+                claimHash,
+                expiryTs,
+                Buffer.alloc(0),
+                1
+            );
+            await program.methods
+                .postAttestation(ZK_PASS_SCHEMA_ID, Array.from(claimHash), Buffer.alloc(0), new BN(expiryTs), signature, recid)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(ZK_PASS_SCHEMA_ID),
+                    subject: subject.publicKey,
+                    attestation,
+                    tombstone: tombstonePdaOf(attestation),
+                    issuer: zkPassIssuerPubkey,
+                    signingKey: issuerKeyPda(zkPassIssuerPubkey, keyId),
+                    payer: subject.publicKey,
+                    instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([subject])
+                .rpc();
+            return attestation;
+        };
+        const statusOf = async (subject: PublicKey, attestation: PublicKey, keyId: number) =>
+            (await program.methods
+                .verifyAttestation(ZK_PASS_SCHEMA_ID)
+                .accountsStrict({ config: configPda, subject, attestation, signingKey: issuerKeyPda(zkPassIssuerPubkey, keyId) })
+                .view()).status;
+        const rotate = (newKeyId: number, publicKey: Uint8Array, graceSecs: BN) =>
+            program.methods
+                .rotateIssuerKey(newKeyId, { secp256k1: {} }, Array.from(publicKey), graceSecs)
+                .accountsStrict({
+                    config: configPda,
+                    oldKey: issuerKeyPda(zkPassIssuerPubkey, 0),
+                    newKey: issuerKeyPda(zkPassIssuerPubkey, newKeyId),
+                    admin: admin.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([admin])
+                .rpc();
+
+        const newSigner = newSecp256k1Key();
+
+        for (const [graceSecs, error] of [
+            [new BN(-1), "InvalidKeyWindow"],
+            [new BN("9223372036854775807"), "MathOverflow"],
+        ] as [BN, string][]) {
+            try {
+                await rotate(1, newSigner.publicKey, graceSecs);
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include(error);
+            }
+        }
+
+        await rotate(1, newSigner.publicKey, new BN(600));
+
+        const oldKey = await program.account.issuerSigningKey.fetch(issuerKeyPda(zkPassIssuerPubkey, 0));
+        const newKey = await program.account.issuerSigningKey.fetch(issuerKeyPda(zkPassIssuerPubkey, 1));
+        expect(oldKey.validUntil.toNumber()).to.equal(newKey.validFrom.toNumber() + 600);
+        expect(newKey.validUntil.toNumber()).to.equal(0);
+        expect(Buffer.from(newKey.publicKey)).to.deep.equal(Buffer.from(newSigner.publicKey));
+
+        // The old key keeps signing through the grace period alongside the new one
+        const oldKeySubject = anchor.web3.Keypair.generate();
+        const newKeySubject = anchor.web3.Keypair.generate();
+        await airdrop(oldKeySubject.publicKey);
+        await airdrop(newKeySubject.publicKey);
+        const signedByOldKey = await post(oldKeySubject, 0, zkPassSigner.privateKey);
+        const signedByNewKey = await post(newKeySubject, 1, newSigner.privateKey);
+
+        const retireAccounts = {
+            config: configPda,
+            signingKey: issuerKeyPda(zkPassIssuerPubkey, 1),
+            admin: admin.publicKey,
+        };
+        await program.methods.retireIssuerKey().accountsStrict(retireAccounts).signers([admin]).rpc();
+
+        const retiredKey = await program.account.issuerSigningKey.fetch(issuerKeyPda(zkPassIssuerPubkey, 1));
+        expect(retiredKey.retired).to.equal(true);
+        expect(retiredKey.retiredAt.toNumber()).to.be.greaterThan(0);
+
+        // Only what the retired key signed is revoked
+        expect(await statusOf(newKeySubject.publicKey, signedByNewKey, 1)).to.deep.equal({ revoked: {} });
+        expect(await statusOf(oldKeySubject.publicKey, signedByOldKey, 0)).to.deep.equal({ valid: {} });
+
+        // The key account has to be the one that signed
+        try {
+            await statusOf(newKeySubject.publicKey, signedByNewKey, 0);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("SigningKeyMismatch");
+        }
+
+        const lateSubject = anchor.web3.Keypair.generate();
+        await airdrop(lateSubject.publicKey);
+        try {
+            await post(lateSubject, 1, newSigner.privateKey);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("SigningKeyInactive");
+        }
+
+        try {
+            await program.methods.retireIssuerKey().accountsStrict(retireAccounts).signers([admin]).rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("SigningKeyInactive");
+        }
+    });
+
+    it("Sets schema quorums and counts distinct valid attestations", async () => {
+        const quorumSchemaId = 20;
+        const subject = anchor.web3.Keypair.generate();
+        await airdrop(subject.publicKey);
+
+        await program.methods
+            .addSchema(quorumSchemaId, "quorumKyc", 1, Array(32).fill(0), new anchor.BN(1800), true, false, 0, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(quorumSchemaId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+
+        // Two Ethereum issuers and one Solana issuer, all signing with secp256k1 keys
+        const issuers = [
+            { pubkey: anchor.web3.Keypair.generate().publicKey, issuerType: { ethereum: {} } },
+            { pubkey: anchor.web3.Keypair.generate().publicKey, issuerType: { ethereum: {} } },
+            { pubkey: anchor.web3.Keypair.generate().publicKey, issuerType: { solana: {} } },
+        ];
+        const expiryTs = Math.floor(Date.now() / 1000) + 1800;
+        const attestations: PublicKey[] = [];
+        const signingKeyOf = new Map<string, PublicKey>();
+        for (const { pubkey, issuerType } of issuers) {
+            await program.methods
+                .addIssuer(pubkey, issuerType)
+                .accounts({ config: configPda, admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+            await program.methods
+                .allowIssuerSchema(pubkey, quorumSchemaId)
+                .accounts({ config: configPda, admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+            const signer = newSecp256k1Key();
+            await addSecp256k1Key(pubkey, 0, signer.publicKey);
+
+            const claimHash = Buffer.alloc(32, attestations.length + 1);
+            const { signature, recid } = signPost(
+                signer.privateKey, subject.publicKey, quorumSchemaId, pubkey, claimHash, expiryTs, Buffer.alloc(0), 1
+            );
+            const attestation = attestationPdaOf(subject.publicKey, quorumSchemaId, pubkey);
+            await program.methods
+                .postAttestation(quorumSchemaId, Array.from(claimHash), Buffer.alloc(0), new BN(expiryTs), signature, recid)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(quorumSchemaId),
+                    subject: subject.publicKey,
+                    attestation,
+                    tombstone: tombstonePdaOf(attestation),
+                    issuer: pubkey,
+                    signingKey: issuerKeyPda(pubkey, 0),
+                    payer: subject.publicKey,
+                    instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([subject])
+                .rpc();
+            attestations.push(attestation);
+            signingKeyOf.set(attestation.toBase58(), issuerKeyPda(pubkey, 0));
+        }
+        const [ethA, ethB, solC] = attestations;
+
+        const [quorumPda] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("quorum"), schemaIdSeed(quorumSchemaId)],
+            program.programId
+        );
+        const setQuorum = (mode: any, threshold: number, named: PublicKey[]) =>
+            program.methods
+                .setSchemaQuorum(quorumSchemaId, mode, threshold, named)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(quorumSchemaId),
+                    quorum: quorumPda,
+                    admin: admin.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([admin])
+                .rpc();
+        // Each candidate is followed by the key that signed it
+        const checkQuorum = (candidates: PublicKey[], subjectKey = subject.publicKey, keys = candidates.map(
+            (pubkey) => signingKeyOf.get(pubkey.toBase58()) ?? PublicKey.default
+        )) =>
+            program.methods
+                .checkQuorum(quorumSchemaId)
+                .accountsStrict({ config: configPda, quorum: quorumPda, subject: subjectKey })
+                .remainingAccounts(candidates.flatMap((pubkey, i) => [
+                    { pubkey, isSigner: false, isWritable: false },
+                    { pubkey: keys[i], isSigner: false, isWritable: false },
+                ]))
+                .view();
+
+        const named = { namedIssuers: {} };
+        const distinct = { distinctIssuerTypes: {} };
+        for (const [mode, threshold, list] of [
+            [named, 0, [issuers[0].pubkey]],
+            [named, 3, [issuers[0].pubkey, issuers[1].pubkey]],
+            [named, 2, [issuers[0].pubkey, issuers[0].pubkey]],
+            [distinct, 1, [issuers[0].pubkey]],
+            [distinct, 4, []], // two issuer types plus proofs
+        ] as [any, number, PublicKey[]][]) {
+            try {
+                await setQuorum(mode, threshold, list);
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include("InvalidQuorum");
+            }
+        }
+
+        // Two of the two Ethereum issuers
+        await setQuorum(named, 2, [issuers[0].pubkey, issuers[1].pubkey]);
+
+        let result = await checkQuorum([ethA, ethA]);
+        expect(result.matched).to.equal(1);
+        expect(result.qualified).to.equal(false);
+
+        result = await checkQuorum([ethA, solC]);
+        expect(result.matched).to.equal(1);
+
+        result = await checkQuorum([ethA, ethB]);
+        expect(result.matched).to.equal(2);
+        expect(result.qualified).to.equal(true);
+
+        // Candidates must be this subject's attestations under this schema
+        for (const [candidates, subjectKey] of [
+            [[ethA], anchor.web3.Keypair.generate().publicKey],
+            [[configPda], subject.publicKey],
+        ] as [PublicKey[], PublicKey][]) {
+            try {
+                await checkQuorum(candidates, subjectKey);
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include("InvalidAttestation");
+            }
+        }
+        try {
+            await checkQuorum([ethA, ethB], subject.publicKey, [signingKeyOf.get(ethB.toBase58())!, signingKeyOf.get(ethB.toBase58())!]);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("SigningKeyMismatch");
+        }
+
+        await program.methods
+            .revokeMyAttestation()
+            .accountsStrict({ config: configPda, subject: subject.publicKey, attestation: ethB })
+            .signers([subject])
+            .rpc();
+
+        result = await checkQuorum([ethA, ethB]);
+        expect(result.matched).to.equal(1);
+        expect(result.qualified).to.equal(false);
+
+        // Proofs are a third source, so a threshold of three is reachable
+        await setQuorum(distinct, 3, []);
+        const quorum = await program.account.schemaQuorum.fetch(quorumPda);
+        expect(quorum.mode).to.deep.equal(distinct);
+        expect(quorum.threshold).to.equal(3);
+
+        await setQuorum(distinct, 2, []);
+        result = await checkQuorum([ethA, ethB]);
+        expect(result.matched).to.equal(1);
+
+        result = await checkQuorum([ethA, solC, solC]);
+        expect(result.matched).to.equal(2);
+        expect(result.qualified).to.equal(true);
+    });
+
+    it("Closes expired attestations and revoked ones past retention", async () => {
+        const closeSchemaId = 30;
+        const issuer = anchor.web3.Keypair.generate().publicKey;
+        const signer = newSecp256k1Key();
+        const payer = anchor.web3.Keypair.generate();
+        await airdrop(payer.publicKey);
+
+        await program.methods
+            .addSchema(closeSchemaId, "closeKyc", 1, Array(32).fill(0), new anchor.BN(1800), true, false, 0, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(closeSchemaId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+        await program.methods
+            .addIssuer(issuer, { ethereum: {} })
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        await program.methods
+            .allowIssuerSchema(issuer, closeSchemaId)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        await addSecp256k1Key(issuer, 0, signer.publicKey);
+
+        const chainTime = async () => (await provider.connection.getBlockTime(await provider.connection.getSlot()))!;
+        const post = async (subject: anchor.web3.Keypair, expiryTs: number, version = 1) => {
+            const claimHash = Buffer.alloc(32, 5);
+            const { signature, recid } = signPost(
+                signer.privateKey, subject.publicKey, closeSchemaId, issuer, claimHash, expiryTs, Buffer.alloc(0), version
+            );
+            const attestation = attestationPdaOf(subject.publicKey, closeSchemaId, issuer);
+            await program.methods
+                .postAttestation(closeSchemaId, Array.from(claimHash), Buffer.alloc(0), new BN(expiryTs), signature, recid)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(closeSchemaId),
+                    subject: subject.publicKey,
+                    attestation,
+                    tombstone: tombstonePdaOf(attestation),
+                    issuer,
+                    signingKey: issuerKeyPda(issuer, 0),
+                    payer: payer.publicKey,
+                    instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([payer])
+                .rpc();
+            return attestation;
+        };
+        const close = (attestation: PublicKey) =>
+            program.methods
+                .closeExpiredAttestation()
+                .accountsStrict({
+                    config: configPda,
+                    attestation,
+                    schema: schemaPda(closeSchemaId),
+                    signingKey: issuerKeyPda(issuer, 0),
+                    history: null,
+                    payer: payer.publicKey,
+                })
+                .rpc();
+        const setRetention = (secs: BN) =>
+            program.methods
+                .setRevocationRetention(secs)
+                .accounts({ config: configPda, admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+        const expectStillActive = async (attestation: PublicKey) => {
+            try {
+                await close(attestation);
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include("AttestationStillActive");
+            }
+        };
+
+        const shortLived = anchor.web3.Keypair.generate();
+        const revokedSubject = anchor.web3.Keypair.generate();
+        const expiring = await post(shortLived, (await chainTime()) + 2);
+        const revokedExpiry = (await chainTime()) + 1800;
+        const revoked = await post(revokedSubject, revokedExpiry);
+
+        await expectStillActive(revoked);
+
+        await program.methods
+            .revokeMyAttestation()
+            .accountsStrict({ config: configPda, subject: revokedSubject.publicKey, attestation: revoked })
+            .signers([revokedSubject])
+            .rpc();
+
+        // Still inside the default 30-day retention window
+        await expectStillActive(revoked);
+
+        // A retention this long must not wrap around and make the attestation closable
+        await setRetention(new BN("9223372036854775807"));
+        await expectStillActive(revoked);
+
+        await setRetention(new BN(0));
+        const before = await provider.connection.getBalance(payer.publicKey);
+        await close(revoked);
+        expect(await provider.connection.getAccountInfo(revoked)).to.be.null;
+        expect(await provider.connection.getBalance(payer.publicKey)).to.be.greaterThan(before);
+
+        // Closing doesn't reset the version, so the revoked signature can't be replayed
+        try {
+            await post(revokedSubject, revokedExpiry);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidSignature");
+        }
+
+        const { expiryTs } = await program.account.attestation.fetch(expiring);
+        while ((await chainTime()) < expiryTs.toNumber()) {
+            await new Promise((resolve) => setTimeout(resolve, 500));
+        }
+        await close(expiring);
+        expect(await provider.connection.getAccountInfo(expiring)).to.be.null;
+        expect((await program.account.schemaDefinition.fetch(schemaPda(closeSchemaId))).attestationCount).to.equal(0);
+
+        // Bulk revocation starts the same retention window as an individual one
+        const bulkRevoked = await post(anchor.web3.Keypair.generate(), (await chainTime()) + 1800);
+        await setRetention(new BN(30 * 24 * 60 * 60));
+        await program.methods
+            .revokeIssuerAttestations(issuer, { keyCompromise: {} })
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        await expectStillActive(bulkRevoked);
+
+        await setRetention(new BN(0));
+        await close(bulkRevoked);
+        expect(await provider.connection.getAccountInfo(bulkRevoked)).to.be.null;
+
+        await setRetention(new BN(30 * 24 * 60 * 60));
+    });
+
+    it("Stores a schema-typed payload and resizes on re-post", async () => {
+        const dataSchemaId = 40;
+        await program.methods
+            .addSchema(dataSchemaId, "incomeDetail", 1, Array(32).fill(0), new anchor.BN(1800), true, false, 64, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(dataSchemaId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+        const { issuer, signer } = await registerSecp256k1Issuer(dataSchemaId);
+
+        const subject = anchor.web3.Keypair.generate();
+        await airdrop(subject.publicKey);
+        const attestationPda = attestationPdaOf(subject.publicKey, dataSchemaId, issuer);
+        const claimHash = Buffer.alloc(32, 11);
+        const expiryTs = Math.floor(Date.now() / 1000) + 1800;
+
+        const accounts = {
+            config: configPda,
+            schema: schemaPda(dataSchemaId),
+            subject: subject.publicKey,
+            attestation: attestationPda,
+            tombstone: tombstonePdaOf(attestationPda),
+            issuer,
+            signingKey: issuerKeyPda(issuer, 0),
+            payer: subject.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        };
+        const post = (data: Buffer) => {
+            const { signature, recid } = signPost(
+                signer.privateKey, subject.publicKey, dataSchemaId, issuer, claimHash, expiryTs, data, 1
+            );
+            return program.methods
+                .postAttestation(dataSchemaId, Array.from(claimHash), data, new BN(expiryTs), signature, recid)
+                .accountsStrict(accounts)
+                .signers([subject])
+                .rpc();
+        };
+        const repost = (data: Buffer, signedData: Buffer = data) => {
+            const { signature, recid } = signPost(
+                signer.privateKey, subject.publicKey, dataSchemaId, issuer, claimHash, expiryTs, signedData, 2
+            );
+            return program.methods
+                .repostAttestation(dataSchemaId, Array.from(claimHash), data, new BN(expiryTs), signature, recid)
+                .accountsStrict({ ...accounts, history: null })
+                .signers([subject])
+                .rpc();
+        };
+
+        try {
+            await post(Buffer.alloc(65, 1));
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("DataTooLarge");
+        }
+
+        const payload = Buffer.from("band=3;employer=acme;months=18");
+        await post(payload);
+
+        let attestation = await program.account.attestation.fetch(attestationPda);
+        expect(Buffer.from(attestation.data)).to.deep.equal(payload);
+        const initialSize = (await provider.connection.getAccountInfo(attestationPda))!.data.length;
+
+        // A second first-post can't overwrite the account
+        try {
+            await post(payload);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("already in use");
+        }
+
+        // The signature covers the payload, so it can't be swapped for another one
+        try {
+            await repost(Buffer.alloc(payload.length, 7), payload);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidSignature");
+        }
+
+        const largerPayload = Buffer.concat([payload, Buffer.from(";verified=payroll-api;v=2")]);
+        await repost(largerPayload);
+
+        attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.version).to.equal(2);
+        expect(Buffer.from(attestation.data)).to.deep.equal(largerPayload);
+
+        const resized = (await provider.connection.getAccountInfo(attestationPda))!;
+        expect(resized.data.length).to.equal(initialSize + largerPayload.length - payload.length);
+        expect(resized.lamports).to.be.at.least(
+            await provider.connection.getMinimumBalanceForRentExemption(resized.data.length)
+        );
+    });
+
+    it("Rejects posts under schemas the issuer isn't allowed to use", async () => {
+        const dataSchemaId = 40;
+        const { issuer, signer } = await registerSecp256k1Issuer(ZK_PASS_SCHEMA_ID);
+
+        const subject = anchor.web3.Keypair.generate();
+        await airdrop(subject.publicKey);
+        const claimHash = Buffer.alloc(32, 12);
+        const expiryTs = Math.floor(Date.now() / 1000) + 1800;
+        const { signature, recid } = signPost(
+            signer.privateKey, subject.publicKey, dataSchemaId, issuer, claimHash, expiryTs, Buffer.alloc(0), 1
+        );
+        const post = () =>
+            program.methods
+                .postAttestation(dataSchemaId, Array.from(claimHash), Buffer.alloc(0), new BN(expiryTs), signature, recid)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(dataSchemaId),
+                    subject: subject.publicKey,
+                    attestation: attestationPdaOf(subject.publicKey, dataSchemaId, issuer),
+                    tombstone: tombstonePdaOf(attestationPdaOf(subject.publicKey, dataSchemaId, issuer)),
+                    issuer,
+                    signingKey: issuerKeyPda(issuer, 0),
+                    payer: subject.publicKey,
+                    instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([subject])
+                .rpc();
+
+        try {
+            await post();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("IssuerSchemaNotAuthorized");
+        }
+
+        await program.methods
+            .allowIssuerSchema(issuer, dataSchemaId)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        await post();
+
+        const attestation = await program.account.attestation.fetch(
+            attestationPdaOf(subject.publicKey, dataSchemaId, issuer)
+        );
+        expect(attestation.issuer.toBase58()).to.equal(issuer.toBase58());
+    });
+
+    it("Requires the subject's signature on consent schemas", async () => {
+        const consentSchemaId = 41;
+        await program.methods
+            .addSchema(consentSchemaId, "consentedKyc", 1, Array(32).fill(0), new anchor.BN(1800), true, true, 0, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(consentSchemaId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+        const { issuer, signer } = await registerSecp256k1Issuer(consentSchemaId);
+
+        // A relayer pays; the subject only signs to consent
+        const payer = anchor.web3.Keypair.generate();
+        await airdrop(payer.publicKey);
+        const subject = anchor.web3.Keypair.generate();
+        const attestationPda = attestationPdaOf(subject.publicKey, consentSchemaId, issuer);
+        const claimHash = Buffer.alloc(32, 13);
+        const expiryTs = Math.floor(Date.now() / 1000) + 1800;
+        const { signature, recid } = signPost(
+            signer.privateKey, subject.publicKey, consentSchemaId, issuer, claimHash, expiryTs, Buffer.alloc(0), 1
+        );
+        const postIx = () =>
+            program.methods
+                .postAttestation(consentSchemaId, Array.from(claimHash), Buffer.alloc(0), new BN(expiryTs), signature, recid)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(consentSchemaId),
+                    subject: subject.publicKey,
+                    attestation: attestationPda,
+                    tombstone: tombstonePdaOf(attestationPda),
+                    issuer,
+                    signingKey: issuerKeyPda(issuer, 0),
+                    payer: payer.publicKey,
+                    instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .instruction();
+
+        try {
+            await provider.sendAndConfirm(new anchor.web3.Transaction().add(await postIx()), [payer]);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect([err.toString(), ...(err.logs ?? [])].join("\n")).to.include("SubjectConsentRequired");
+        }
+
+        const consentedIx = await postIx();
+        consentedIx.keys
+            .filter((meta) => meta.pubkey.equals(subject.publicKey))
+            .forEach((meta) => (meta.isSigner = true));
+        await provider.sendAndConfirm(new anchor.web3.Transaction().add(consentedIx), [payer, subject]);
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.subject.toBase58()).to.equal(subject.publicKey.toBase58());
+        expect(attestation.payer.toBase58()).to.equal(payer.publicKey.toBase58());
+        expect(await provider.connection.getBalance(subject.publicKey)).to.equal(0);
+    });
+
+    it("Posts a Merkle batch and materializes a leaf", async () => {
+        const batchSchemaId = 10;
+        const batchIssuer = anchor.web3.Keypair.generate();
+
+        await program.methods
+            .addSchema(batchSchemaId, "batchKyc", 1, Array(32).fill(0), new anchor.BN(1800), true, false, 0, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(batchSchemaId),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .addIssuer(batchIssuer.publicKey, { solana: {} })
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .allowIssuerSchema(batchIssuer.publicKey, batchSchemaId)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        const expiryTs = Math.floor(Date.now() / 1000) + 1200;
+        const subjects = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+        const claimHashes = [Buffer.alloc(32, 7), Buffer.alloc(32, 9)];
+
+        const leafOf = (subject: PublicKey, claimHash: Buffer) => {
+            const expiry = Buffer.alloc(8);
+            expiry.writeBigInt64LE(BigInt(expiryTs));
+            return Buffer.from(sha3.keccak_256.digest(Buffer.concat([
+                subject.toBuffer(),
+                schemaIdSeed(batchSchemaId),
+                claimHash,
+                expiry,
+            ])));
+        };
+        const leaves = subjects.map((kp, i) => leafOf(kp.publicKey, claimHashes[i]));
+        const sortedPair = Buffer.compare(leaves[0], leaves[1]) <= 0 ? leaves : [leaves[1], leaves[0]];
+        const root = Buffer.from(sha3.keccak_256.digest(Buffer.concat(sortedPair)));
+
+        const batchId = new BN(1);
+        const [batchPda] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("batch"), batchIssuer.publicKey.toBuffer(), batchId.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        );
+        const verifyAccounts = (subject: PublicKey) => {
+            const attestation = attestationPdaOf(subject, batchSchemaId, batchIssuer.publicKey);
+            return {
+                config: configPda,
+                schema: schemaPda(batchSchemaId),
+                batch: batchPda,
+                signingKey: issuerKeyPda(batchIssuer.publicKey, 0),
+                subject,
+                attestation,
+                tombstone: tombstonePdaOf(attestation),
+            };
+        };
+        const expectLeafRevoked = async () => {
+            try {
+                await program.methods
+                    .verifyInBatch(batchSchemaId, Array.from(claimHashes[1]), new BN(expiryTs), [Array.from(leaves[0])])
+                    .accountsStrict(verifyAccounts(subjects[1].publicKey))
+                    .rpc();
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include("LeafRevoked");
+            }
+        };
+
+        await program.methods
+            .addIssuerKey(
+                batchIssuer.publicKey,
+                0,
+                { ed25519: {} },
+                Array.from(Buffer.concat([batchIssuer.publicKey.toBuffer(), Buffer.alloc(33)])),
+                new BN(0),
+                new BN(0)
+            )
+            .accountsStrict({
+                config: configPda,
+                signingKey: issuerKeyPda(batchIssuer.publicKey, 0),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+
+        const batchMessage = Buffer.from(sha3.keccak_256.digest(Buffer.concat([
+            program.programId.toBuffer(),
+            batchIssuer.publicKey.toBuffer(),
+            root,
+            batchId.toArrayLike(Buffer, "le", 8),
+        ])));
+        const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: batchIssuer.secretKey,
+            message: batchMessage,
+        });
+        // web3.js lays out [header(16) | public key(32) | signature(64) | message]
+        const batchSignature = ed25519Ix.data.subarray(48, 112);
+
+        await program.methods
+            .postAttestationBatch(batchId, Array.from(root), 2, Array.from(batchSignature), 0)
+            .accountsStrict({
+                config: configPda,
+                payer: admin.publicKey,
+                issuer: batchIssuer.publicKey,
+                signingKey: issuerKeyPda(batchIssuer.publicKey, 0),
+                batch: batchPda,
+                instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .preInstructions([ed25519Ix])
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .verifyInBatch(batchSchemaId, Array.from(claimHashes[0]), new BN(expiryTs), [Array.from(leaves[1])])
+            .accountsStrict(verifyAccounts(subjects[0].publicKey))
+            .rpc();
+
+        try {
+            await program.methods
+                .verifyInBatch(batchSchemaId, Array.from(claimHashes[1]), new BN(expiryTs), [Array.from(leaves[1])])
+                .accountsStrict(verifyAccounts(subjects[0].publicKey))
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidMerkleProof");
+        }
+
+        const [attestationPda] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("attest"),
+                subjects[1].publicKey.toBuffer(),
+                schemaIdSeed(batchSchemaId),
+                batchIssuer.publicKey.toBuffer(),
+            ],
+            program.programId
+        );
+
+        await program.methods
+            .materializeAttestation(batchSchemaId, Array.from(claimHashes[1]), new BN(expiryTs), [Array.from(leaves[0])])
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(batchSchemaId),
+                batch: batchPda,
+                signingKey: issuerKeyPda(batchIssuer.publicKey, 0),
+                payer: admin.publicKey,
+                subject: subjects[1].publicKey,
+                attestation: attestationPda,
+                tombstone: tombstonePdaOf(attestationPda),
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.issuer.toBase58()).to.equal(batchIssuer.publicKey.toBase58());
+        expect(attestation.expiryTs.toNumber()).to.equal(expiryTs);
+
+        await program.methods
+            .revokeMyAttestation()
+            .accountsStrict({
+                config: configPda,
+                subject: subjects[1].publicKey,
+                attestation: attestationPda,
+            })
+            .signers([subjects[1]])
+            .rpc();
+
+        const revoked = await program.account.attestation.fetch(attestationPda);
+        expect(revoked.revoked).to.eq(true);
+        expect(revoked.revocationReason).to.deep.equal({ userRequest: {} });
+
+        // Revoking the materialized account revokes the leaf as well
+        await expectLeafRevoked();
+
+        // The schema can't be removed out from under an open attestation
+        expect((await program.account.schemaDefinition.fetch(schemaPda(batchSchemaId))).attestationCount).to.equal(1);
+        try {
+            await program.methods
+                .removeSchema(batchSchemaId)
+                .accountsStrict({ config: configPda, schema: schemaPda(batchSchemaId), admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("SchemaInUse");
+        }
+
+        await program.methods
+            .closeAttestation()
+            .accountsStrict({
+                config: configPda,
+                subject: subjects[1].publicKey,
+                attestation: attestationPda,
+                schema: schemaPda(batchSchemaId),
+                history: null,
+            })
+            .signers([subjects[1]])
+            .rpc();
+
+        expect(await program.account.attestation.fetchNullable(attestationPda)).to.be.null;
+        expect((await program.account.schemaDefinition.fetch(schemaPda(batchSchemaId))).attestationCount).to.equal(0);
+
+        // Closing it doesn't bring the leaf back, and neither does materializing it again
+        await expectLeafRevoked();
+        try {
+            await program.methods
+                .materializeAttestation(batchSchemaId, Array.from(claimHashes[1]), new BN(expiryTs), [Array.from(leaves[0])])
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(batchSchemaId),
+                    batch: batchPda,
+                    signingKey: issuerKeyPda(batchIssuer.publicKey, 0),
+                    payer: admin.publicKey,
+                    subject: subjects[1].publicKey,
+                    attestation: attestationPda,
+                    tombstone: tombstonePdaOf(attestationPda),
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("LeafAlreadyMaterialized");
+        }
+
+        await program.methods
+            .revokeIssuerAttestations(batchIssuer.publicKey, { keyCompromise: {} })
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        const config = await program.account.config.fetch(configPda);
+        const issuerEntry = config.issuers.find((i: any) => i.pubkey.equals(batchIssuer.publicKey));
+        expect(issuerEntry.revocationEpoch).to.equal(1);
+
+        try {
+            await program.methods
+                .verifyInBatch(batchSchemaId, Array.from(claimHashes[0]), new BN(expiryTs), [Array.from(leaves[1])])
+                .accountsStrict(verifyAccounts(subjects[0].publicKey))
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("RevokedByIssuerEpoch");
+        }
+    });
+
+    it("Approves a bonded issuer application, slashes its bond and refunds the rest", async () => {
+        const applicant = anchor.web3.Keypair.generate();
+        await airdrop(applicant.publicKey);
+
+        const bondMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+        const applicantAta = await createAssociatedTokenAccount(provider.connection, applicant, bondMint, applicant.publicKey);
+        const treasuryAta = await createAssociatedTokenAccount(provider.connection, admin, bondMint, admin.publicKey);
+        await mintTo(provider.connection, admin, bondMint, applicantAta, admin, 5_000_000);
+
+        await program.methods
+            .setIssuerBond(bondMint, new BN(1_000_000))
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        const applicationPdaOf = (applicantKey: PublicKey, issuer: PublicKey) =>
+            anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("issuer_application"), applicantKey.toBuffer(), issuer.toBuffer()],
+                program.programId
+            )[0];
+        const applicationPda = applicationPdaOf(applicant.publicKey, applicant.publicKey);
+        const bondVault = getAssociatedTokenAddressSync(bondMint, applicationPda, true);
+
+        // Applying for someone else's identity doesn't take the slot away from them
+        const squatter = anchor.web3.Keypair.generate();
+        await airdrop(squatter.publicKey);
+        const squatterAta = await createAssociatedTokenAccount(provider.connection, squatter, bondMint, squatter.publicKey);
+        await mintTo(provider.connection, admin, bondMint, squatterAta, admin, 1_000_000);
+        const squatterApplication = applicationPdaOf(squatter.publicKey, applicant.publicKey);
+        const squatterVault = getAssociatedTokenAddressSync(bondMint, squatterApplication, true);
+        await program.methods
+            .submitIssuerApplication(applicant.publicKey, { solana: {} }, "https://squatter.example/meta.json", Array(32).fill(2), new BN(1_000_000))
+            .accountsStrict({
+                applicant: squatter.publicKey,
+                application: squatterApplication,
+                bondMint,
+                applicantToken: squatterAta,
+                bondVault: squatterVault,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([squatter])
+            .rpc();
+
+        const submitAccounts = {
+            config: configPda,
+            applicant: applicant.publicKey,
+            application: applicationPda,
+            bondMint,
+            applicantToken: applicantAta,
+            bondVault,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        };
+
+        try {
+            await program.methods
+                .submitIssuerApplication(applicant.publicKey, { solana: {} }, "https://issuer.example/meta.json", Array(32).fill(1), new BN(999_999))
+                .accountsStrict(submitAccounts)
+                .signers([applicant])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InsufficientBond");
+        }
+
+        await program.methods
+            .submitIssuerApplication(applicant.publicKey, { solana: {} }, "https://issuer.example/meta.json", Array(32).fill(1), new BN(2_000_000))
+            .accountsStrict(submitAccounts)
+            .signers([applicant])
+            .rpc();
+
+        expect(Number((await getAccount(provider.connection, bondVault)).amount)).to.equal(2_000_000);
+
+        await program.methods
+            .approveIssuerApplication()
+            .accountsStrict({ config: configPda, admin: admin.publicKey, application: applicationPda })
+            .signers([admin])
+            .rpc();
+
+        const config = await program.account.config.fetch(configPda);
+        const issuerEntry = config.issuers.find((i: any) => i.pubkey.equals(applicant.publicKey));
+        expect(issuerEntry.enabled).to.equal(true);
+        expect(issuerEntry.allowedSchemas).to.deep.equal([]);
+
+        const slashAccounts = {
+            config: configPda,
+            admin: admin.publicKey,
+            application: applicationPda,
+            bondMint,
+            bondVault,
+            destination: treasuryAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        };
+
+        await program.methods
+            .slashIssuerBond(new BN(1_500_000))
+            .accountsStrict(slashAccounts)
+            .signers([admin])
+            .rpc();
+
+        const application = await program.account.issuerApplication.fetch(applicationPda);
+        expect(application.status).to.deep.equal({ approved: {} });
+        expect(application.slashedAmount.toNumber()).to.equal(1_500_000);
+        expect(Number((await getAccount(provider.connection, treasuryAta)).amount)).to.equal(1_500_000);
+
+        try {
+            await program.methods
+                .slashIssuerBond(new BN(500_001))
+                .accountsStrict(slashAccounts)
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("SlashExceedsBond");
+        }
+
+        // The squatter's application can never be approved, but its bond isn't stuck
+        await program.methods
+            .withdrawIssuerApplication()
+            .accountsStrict({
+                applicant: squatter.publicKey,
+                application: squatterApplication,
+                bondMint,
+                applicantToken: squatterAta,
+                bondVault: squatterVault,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([squatter])
+            .rpc();
+        expect(Number((await getAccount(provider.connection, squatterAta)).amount)).to.equal(1_000_000);
+        expect(await provider.connection.getAccountInfo(squatterApplication)).to.be.null;
+
+        const withdrawAccounts = {
+            applicant: applicant.publicKey,
+            application: applicationPda,
+            bondMint,
+            applicantToken: applicantAta,
+            bondVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        };
+
+        const releaseAccounts = { config: configPda, admin: admin.publicKey, application: applicationPda };
+        const expectError = async (attempt: Promise<unknown>, error: string) => {
+            try {
+                await attempt;
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include(error);
+            }
+        };
+        const withdraw = () =>
+            program.methods
+                .withdrawIssuerApplication()
+                .accountsStrict(withdrawAccounts)
+                .signers([applicant])
+                .rpc();
+
+        await expectError(withdraw(), "BondNotReleased");
+        await expectError(
+            program.methods.releaseIssuerBond().accountsStrict(releaseAccounts).signers([admin]).rpc(),
+            "IssuerStillRegistered"
+        );
+
+        await program.methods
+            .removeIssuer(applicant.publicKey)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        // Removal alone doesn't free the bond, so a slash can still land afterwards
+        await expectError(withdraw(), "BondNotReleased");
+        await program.methods
+            .slashIssuerBond(new BN(250_000))
+            .accountsStrict(slashAccounts)
+            .signers([admin])
+            .rpc();
+
+        await program.methods.releaseIssuerBond().accountsStrict(releaseAccounts).signers([admin]).rpc();
+        expect((await program.account.issuerApplication.fetch(applicationPda)).status).to.deep.equal({ released: {} });
+        await expectError(
+            program.methods.slashIssuerBond(new BN(1)).accountsStrict(slashAccounts).signers([admin]).rpc(),
+            "ApplicationNotApproved"
+        );
+
+        await withdraw();
+
+        // 5.0 minted, 2.0 bonded, 1.75 slashed
+        expect(Number((await getAccount(provider.connection, applicantAta)).amount)).to.equal(3_250_000);
+        expect(await provider.connection.getAccountInfo(applicationPda)).to.be.null;
+        expect(await provider.connection.getAccountInfo(bondVault)).to.be.null;
+    });
+
+    it("Self-posts an Anon Aadhaar attestation with a Groth16 proof", async () => {
+        const ANON_AADHAAR_SCHEMA_ID = 0;
+
+        // Degenerate key with every point a generator: A = (3 + claim) * G1 satisfies the pairing
+        // check. The subject and timestamp inputs use the point at infinity so A doesn't depend on them.
+        const g1 = hexToBytes("0000000000000000000000000000000000000000000000000000000000000001" +
+            "0000000000000000000000000000000000000000000000000000000000000002");
+        const infinity = new Uint8Array(64);
+        const g2 = hexToBytes("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2" +
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed" +
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b" +
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
+        const proofA = hexToBytes("228e687a379ba154554040f8821f4e41ee2be287c201aa9c3bc02c9dd12f1e69" +
+            "1e0fd6ee672d04cfd924ed8fdc7ba5f2d06c53c1edc30f65f2af5a5b97f0a76a");
+        const claimInput = Array(32).fill(0);
+        claimInput[31] = 42;
+
+        const subjectSignal = (subject: PublicKey) => {
+            const signal = Array.from(Buffer.from(sha3.keccak_256.digest(subject.toBuffer())));
+            signal[0] &= 0x1f;
+            return signal;
+        };
+        const timestampInput = (ts: number) => {
+            const input = Buffer.alloc(32);
+            input.writeBigUInt64BE(BigInt(ts), 24);
+            return Array.from(input);
+        };
+        const chainTime = async () => (await provider.connection.getBlockTime(await provider.connection.getSlot()))!;
+
+        await program.methods
+            .addSchema(ANON_AADHAAR_SCHEMA_ID, "anonAadhaar", 1, Array(32).fill(0), new anchor.BN(1800), true, false, 96, null)
+            .accountsStrict({
+                config: configPda,
+                schema: schemaPda(ANON_AADHAAR_SCHEMA_ID),
+                admin: admin.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+
+        const [verifyingKeyPda] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("verifying_key"), schemaIdSeed(ANON_AADHAAR_SCHEMA_ID)],
+            program.programId
+        );
+        const setVerifyingKey = () =>
+            program.methods
+                .setVerifyingKey(ANON_AADHAAR_SCHEMA_ID, Array.from(g1), Array.from(g2), Array.from(g2), Array.from(g2), 3, [Array.from(g1), Array.from(g1)], 1, 2)
+                .accountsStrict({
+                    config: configPda,
+                    schema: schemaPda(ANON_AADHAAR_SCHEMA_ID),
+                    verifyingKey: verifyingKeyPda,
+                    admin: admin.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([admin])
+                .rpc();
+
+        await setVerifyingKey();
+
+        const subject = anchor.web3.Keypair.generate();
+        await airdrop(subject.publicKey);
+
+        const [attestationPda] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("attest"),
+                subject.publicKey.toBuffer(),
+                schemaIdSeed(ANON_AADHAAR_SCHEMA_ID),
+                verifyingKeyPda.toBuffer(),
+            ],
+            program.programId
+        );
+        const postAccounts = {
+            config: configPda,
+            schema: schemaPda(ANON_AADHAAR_SCHEMA_ID),
+            verifyingKey: verifyingKeyPda,
+            subject: subject.publicKey,
+            attestation: attestationPda,
+            history: null,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        };
+        const post = (inputs: number[][], accounts: any = postAccounts, signer = subject) =>
+            program.methods
+                .postZkAttestation(ANON_AADHAAR_SCHEMA_ID, inputs, Array.from(proofA), Array.from(g2), Array.from(g1), new BN(0))
+                .accountsStrict(accounts)
+                .signers([signer])
+                .rpc();
+        const expectError = async (attempt: Promise<unknown>, error: string) => {
+            try {
+                await attempt;
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include(error);
+            }
+        };
+
+        const provenAt = await chainTime();
+        const publicInputs = [claimInput, subjectSignal(subject.publicKey), timestampInput(provenAt)];
+
+        // Until the last IC points land, the missing inputs would count as zero
+        await expectError(post(publicInputs), "VerifyingKeyIncomplete");
+
+        await program.methods
+            .extendVerifyingKey(ANON_AADHAAR_SCHEMA_ID, [Array.from(infinity), Array.from(infinity)])
+            .accountsStrict({ config: configPda, verifyingKey: verifyingKeyPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        // Keys are write-once
+        await expectError(setVerifyingKey(), "custom program error: 0x0");
+
+        const tamperedInputs = [[...claimInput], ...publicInputs.slice(1)];
+        tamperedInputs[0][31] = 43;
+        await expectError(post(tamperedInputs), "InvalidProof");
+        await expectError(post([claimInput, subjectSignal(subject.publicKey), timestampInput(provenAt - 3600)]), "StaleProof");
+
+        // Someone else can't post the subject's proof for themselves
+        const thief = anchor.web3.Keypair.generate();
+        await airdrop(thief.publicKey);
+        const [thiefAttestation] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("attest"), thief.publicKey.toBuffer(), schemaIdSeed(ANON_AADHAAR_SCHEMA_ID), verifyingKeyPda.toBuffer()],
+            program.programId
+        );
+        await expectError(
+            post(publicInputs, { ...postAccounts, subject: thief.publicKey, attestation: thiefAttestation }, thief),
+            "ProofSubjectMismatch"
+        );
+
+        await post(publicInputs);
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.zkVerified).to.equal(true);
+        expect(attestation.issuer.toBase58()).to.equal(verifyingKeyPda.toBase58());
+        expect(Buffer.from(attestation.data)).to.deep.equal(Buffer.concat(publicInputs.map((i) => Buffer.from(i))));
+
+        const verification = await program.methods
+            .verifyAttestation(ANON_AADHAAR_SCHEMA_ID)
+            .accountsStrict({
+                config: configPda,
+                subject: subject.publicKey,
+                attestation: attestationPda,
+                // Proof-backed attestations have no signing key
+                signingKey: PublicKey.default,
+            })
+            .view();
+        expect(verification.status).to.deep.equal({ valid: {} });
+        expect(attestation.version).to.equal(1);
+
+        // Replaying the same proof can't renew the attestation
+        await expectError(post(publicInputs), "StaleProof");
+
+        const [historyPda] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("attest_history"), attestationPda.toBuffer()],
+            program.programId
+        );
+
+        await program.methods
+            .enableAttestationHistory()
+            .accountsStrict({
+                config: configPda,
+                attestation: attestationPda,
+                history: historyPda,
+                authority: subject.publicKey,
+                payer: subject.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([subject])
+            .rpc();
+
+        let reprovenAt = await chainTime();
+        while (reprovenAt <= attestation.issuedAt.toNumber()) {
+            await new Promise((resolve) => setTimeout(resolve, 500));
+            reprovenAt = await chainTime();
+        }
+        const freshInputs = [claimInput, subjectSignal(subject.publicKey), timestampInput(reprovenAt)];
+
+        await expectError(post(freshInputs), "HistoryAccountRequired");
+        await post(freshInputs, { ...postAccounts, history: historyPda });
+
+        const reposted = await program.account.attestation.fetch(attestationPda);
+        expect(reposted.version).to.equal(2);
+
+        const history = await program.account.attestationHistory.fetch(historyPda);
+        expect(history.entries.length).to.equal(1);
+        expect(history.entries[0].version).to.equal(1);
+        expect(history.entries[0].issuedAt.toNumber()).to.equal(attestation.issuedAt.toNumber());
+        expect(history.entries[0].expiryTs.toNumber()).to.equal(attestation.expiryTs.toNumber());
+
+        // Closing archives the last version, and a later attestation at the same
+        // address picks the history back up
+        const close = (history: PublicKey | null) =>
+            program.methods
+                .closeAttestation()
+                .accountsStrict({
+                    config: configPda,
+                    subject: subject.publicKey,
+                    attestation: attestationPda,
+                    schema: schemaPda(ANON_AADHAAR_SCHEMA_ID),
+                    history,
+                })
+                .signers([subject])
+                .rpc();
+        await expectError(close(null), "HistoryAccountRequired");
+        await close(historyPda);
+        expect(await provider.connection.getAccountInfo(attestationPda)).to.be.null;
+
+        let laterProvenAt = await chainTime();
+        while (laterProvenAt <= reprovenAt) {
+            await new Promise((resolve) => setTimeout(resolve, 500));
+            laterProvenAt = await chainTime();
+        }
+        await post([claimInput, subjectSignal(subject.publicKey), timestampInput(laterProvenAt)]);
+        expect((await program.account.attestation.fetch(attestationPda)).historyEnabled).to.equal(false);
+
+        await program.methods
+            .enableAttestationHistory()
+            .accountsStrict({
+                config: configPda,
+                attestation: attestationPda,
+                history: historyPda,
+                authority: subject.publicKey,
+                payer: subject.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([subject])
+            .rpc();
+        expect((await program.account.attestation.fetch(attestationPda)).historyEnabled).to.equal(true);
+
+        const carriedOver = await program.account.attestationHistory.fetch(historyPda);
+        expect(carriedOver.entries.map((e: any) => e.version)).to.deep.equal([1, 2]);
+    });
+
+    it("Rejects out-of-range expiry settings", async () => {
+        try {
+            await program.methods
+                .setMaxExpiry(new BN("9223372036854775808")) // i64::MAX + 1
+                .accounts({ config: configPda, admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidExpiry");
+        }
+
+        // max_expiry_secs above the registry ceiling of 3600
+        try {
+            await program.methods
+                .setSchemaExpiryBounds(ZK_PASS_SCHEMA_ID, new BN(60), new BN(1800), new BN(7200))
+                .accounts({ config: configPda, schema: schemaPda(ZK_PASS_SCHEMA_ID), admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("ExpiryTooFar");
+        }
+
+        await program.methods
+            .setSchemaExpiryBounds(ZK_PASS_SCHEMA_ID, new BN(60), new BN(1800), new BN(2400))
+            .accounts({ config: configPda, schema: schemaPda(ZK_PASS_SCHEMA_ID), admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        const schema = await program.account.schemaDefinition.fetch(schemaPda(ZK_PASS_SCHEMA_ID));
+        expect(schema.minExpirySecs.toNumber()).to.equal(60);
+        expect(schema.defaultExpirySecs.toNumber()).to.equal(1800);
+        expect(schema.maxExpirySecs.toNumber()).to.equal(2400);
+    });
+
+    it("Lets a guardian pause without locking the admin out", async () => {
+        const guardian = anchor.web3.Keypair.generate();
+        const issuer = anchor.web3.Keypair.generate().publicKey;
+        const noFlags = { posting: false, revocation: false, adminOps: false };
+
+        await program.methods
+            .setGuardian(guardian.publicKey)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .addIssuer(issuer, { solana: {} })
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .pause({ posting: true, revocation: false, adminOps: true })
+            .accountsStrict({ config: configPda, authority: guardian.publicKey })
+            .signers([guardian])
+            .rpc();
+
+        let config = await program.account.config.fetch(configPda);
+        expect(config.paused).to.deep.equal({ posting: true, revocation: false, adminOps: true });
+
+        try {
+            await program.methods
+                .setPaused(noFlags)
+                .accounts({ config: configPda, admin: guardian.publicKey })
+                .signers([guardian])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("ConstraintHasOne");
+        }
+
+        try {
+            await program.methods
+                .addIssuer(anchor.web3.Keypair.generate().publicKey, { solana: {} })
+                .accounts({ config: configPda, admin: admin.publicKey })
+                .signers([admin])
+                .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("Paused");
+        }
+
+        // Incident response still works while admin ops are paused
+        await program.methods
+            .removeIssuer(issuer)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        await program.methods
+            .setPaused(noFlags)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+
+        config = await program.account.config.fetch(configPda);
+        expect(config.paused).to.deep.equal(noFlags);
+        expect(config.issuers.some((i: any) => i.pubkey.equals(issuer))).to.equal(false);
+    });
+});
@@ -10,11 +10,20 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { LoansMarketplace } from "../../target/types/loans_marketplace";
+import { ScoreAttestor } from "../../target/types/score_attestor";
 
 describe("loans_marketplace — finalize_funding", () => {
     const provider = AnchorProvider.env();
     anchor.setProvider(provider);
     const program = anchor.workspace.LoansMarketplace as Program<LoansMarketplace>;
+    const scoreProgram = anchor.workspace.ScoreAttestor as Program<ScoreAttestor>;
+
+    // lender_fund always takes the borrower's loan-specific score PDA, initialized or not
+    const loanScorePda = (borrower: PublicKey, loan: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+            [Buffer.from("score"), borrower.toBuffer(), loan.toBuffer()],
+            scoreProgram.programId
+        )[0];
 
     let usdcMint: PublicKey;
     let configPda: PublicKey;
@@ -164,6 +173,8 @@ describe("loans_marketplace — finalize_funding", () => {
                 lenderAta,
                 loanEscrowAta,
                 lenderShare: lenderSharePda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -257,6 +268,8 @@ describe("loans_marketplace — finalize_funding", () => {
                 lenderAta: lender1Ata,
                 loanEscrowAta,
                 lenderShare: lenderShare1Pda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -283,6 +296,8 @@ describe("loans_marketplace — finalize_funding", () => {
                 lenderAta: lender2Ata,
                 loanEscrowAta,
                 lenderShare: lenderShare2Pda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -361,6 +376,8 @@ describe("loans_marketplace — finalize_funding", () => {
                 lenderAta,
                 loanEscrowAta,
                 lenderShare: lenderSharePda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -12,11 +12,20 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { LoansMarketplace } from "../../target/types/loans_marketplace";
+import { ScoreAttestor } from "../../target/types/score_attestor";
 
 describe("loans_marketplace — Full Lifecycle Integration", () => {
     const provider = AnchorProvider.env();
     anchor.setProvider(provider);
     const program = anchor.workspace.LoansMarketplace as Program<LoansMarketplace>;
+    const scoreProgram = anchor.workspace.ScoreAttestor as Program<ScoreAttestor>;
+
+    // lender_fund always takes the borrower's loan-specific score PDA, initialized or not
+    const loanScorePda = (borrower: PublicKey, loan: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+            [Buffer.from("score"), borrower.toBuffer(), loan.toBuffer()],
+            scoreProgram.programId
+        )[0];
 
     let usdcMint: PublicKey;
     let configPda: PublicKey;
@@ -116,6 +125,8 @@ describe("loans_marketplace — Full Lifecycle Integration", () => {
                 lenderAta,
                 loanEscrowAta,
                 lenderShare: lenderSharePda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -282,6 +293,8 @@ describe("loans_marketplace — Full Lifecycle Integration", () => {
                 lenderAta: lender1Ata,
                 loanEscrowAta,
                 lenderShare: lenderShare1Pda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -298,6 +311,8 @@ describe("loans_marketplace — Full Lifecycle Integration", () => {
                 lenderAta: lender2Ata,
                 loanEscrowAta,
                 lenderShare: lenderShare2Pda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -501,6 +516,8 @@ describe("loans_marketplace — Full Lifecycle Integration", () => {
                 lenderAta,
                 loanEscrowAta: loanEscrowAta1,
                 lenderShare: lenderSharePda1,
+                score: loanScorePda(borrower1.publicKey, loanPda1),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -528,6 +545,8 @@ describe("loans_marketplace — Full Lifecycle Integration", () => {
                 lenderAta,
                 loanEscrowAta: loanEscrowAta2,
                 lenderShare: lenderSharePda2,
+                score: loanScorePda(borrower2.publicKey, loanPda2),
+                subjectScore: null,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
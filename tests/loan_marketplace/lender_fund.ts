@@ -9,12 +9,24 @@ import {
     TOKEN_PROGRAM_ID
 } from "@solana/spl-token";
 import { expect } from "chai";
+import * as secp256k1 from "secp256k1";
+import * as sha3 from "js-sha3";
+import { Wallet } from "ethers";
 import { LoansMarketplace } from "../../target/types/loans_marketplace";
+import { ScoreAttestor } from "../../target/types/score_attestor";
 
 describe("loans_marketplace — lender_fund", () => {
     const provider = AnchorProvider.env();
     anchor.setProvider(provider);
     const program = anchor.workspace.LoansMarketplace as Program<LoansMarketplace>;
+    const scoreProgram = anchor.workspace.ScoreAttestor as Program<ScoreAttestor>;
+
+    // lender_fund always takes the borrower's loan-specific score PDA, initialized or not
+    const loanScorePda = (borrower: PublicKey, loan: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+            [Buffer.from("score"), borrower.toBuffer(), loan.toBuffer()],
+            scoreProgram.programId
+        )[0];
 
     let usdcMint: PublicKey;
     let configPda: PublicKey;
@@ -105,7 +117,12 @@ describe("loans_marketplace — lender_fund", () => {
         }
     }
 
-    async function fundLoan(lender: anchor.web3.Keypair, lenderAta: PublicKey, amount: BN) {
+    async function fundLoan(
+        lender: anchor.web3.Keypair,
+        lenderAta: PublicKey,
+        amount: BN,
+        subjectScore: PublicKey | null = null
+    ) {
         const [lenderSharePda] = PublicKey.findProgramAddressSync(
             [Buffer.from("lender_share"), loanPda.toBuffer(), lender.publicKey.toBuffer()],
             program.programId
@@ -121,6 +138,8 @@ describe("loans_marketplace — lender_fund", () => {
                 lenderAta,
                 loanEscrowAta,
                 lenderShare: lenderSharePda,
+                score: loanScorePda(borrower.publicKey, loanPda),
+                subjectScore,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -296,6 +315,256 @@ describe("loans_marketplace — lender_fund", () => {
                 expect(error.message).to.include("insufficient");
             }
         });
+
+        it("requires a score once accepted score models are set", async () => {
+            await createTestLoan();
+            const lender1Ata = await setupLender(lender1);
+            const acceptedModel = anchor.web3.Keypair.generate().publicKey;
+
+            await program.methods
+                .setScoreModels([acceptedModel])
+                .accountsPartial({ config: configPda, admin: provider.wallet.publicKey })
+                .rpc();
+
+            try {
+                await fundLoan(lender1, lender1Ata, PARTIAL_AMOUNT);
+                expect.fail("Should have failed");
+            } catch (error) {
+                expect(error.error.errorCode.code).to.equal("ScoreRequired");
+            } finally {
+                await program.methods
+                    .setScoreModels([])
+                    .accountsPartial({ config: configPda, admin: provider.wallet.publicKey })
+                    .rpc();
+            }
+
+            const config = await program.account.config.fetch(configPda);
+            expect(config.acceptedScoreModels).to.have.length(0);
+        });
+    });
+
+    describe("Score checks", () => {
+        const [scoreConfigPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("score_config")],
+            scoreProgram.programId
+        );
+        // The score config is shared by every suite; each one leaves the provider wallet as its admin
+        const scoreAdmin = { config: scoreConfigPda, admin: provider.wallet.publicKey };
+        const attestor = anchor.web3.Keypair.generate();
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        let modelPda: PublicKey;
+
+        // Mirrors `ScorePayload::message_hash` in score_attestor
+        function scoreMessage(subject: PublicKey, loan: PublicKey, expiryTs: number, nonce: number) {
+            const fields = Buffer.alloc(17);
+            fields.writeUInt16LE(600, 0);
+            fields.writeUInt8(3, 2);
+            fields.writeUInt32LE(400, 3);
+            fields.writeUInt16LE(500, 7);
+            fields.writeBigInt64LE(BigInt(expiryTs), 9);
+            const nonceBuf = Buffer.alloc(8);
+            nonceBuf.writeBigUInt64LE(BigInt(nonce));
+
+            return Buffer.from(sha3.keccak_256.digest(Buffer.concat([
+                scoreProgram.programId.toBuffer(),
+                subject.toBuffer(),
+                loan.toBuffer(),
+                fields,
+                modelPda.toBuffer(),
+                nonceBuf,
+            ])));
+        }
+
+        const historyPdaOf = (subject: PublicKey) =>
+            PublicKey.findProgramAddressSync(
+                [Buffer.from("score_history"), subject.toBuffer()],
+                scoreProgram.programId
+            )[0];
+
+        async function postLoanScore() {
+            const expiryTs = now() + 3600;
+            const sig = secp256k1.ecdsaSign(scoreMessage(borrower.publicKey, loanPda, expiryTs, 1), oracleKey);
+            const scorePda = loanScorePda(borrower.publicKey, loanPda);
+            await scoreProgram.methods
+                .postScoreAttestation(600, 3, 400, 500, new BN(expiryTs), new BN(1), [
+                    { signature: Array.from(sig.signature), recoveryId: sig.recid },
+                ])
+                .accountsStrict({
+                    config: scoreConfigPda,
+                    subject: borrower.publicKey,
+                    loan: loanPda,
+                    model: modelPda,
+                    attestor: attestor.publicKey,
+                    score: scorePda,
+                    history: historyPdaOf(borrower.publicKey),
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([attestor])
+                .rpc();
+            return scorePda;
+        }
+
+        async function postSubjectScore() {
+            const expiryTs = now() + 3600;
+            const sig = secp256k1.ecdsaSign(scoreMessage(borrower.publicKey, PublicKey.default, expiryTs, 1), oracleKey);
+            const [scorePda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("score"), borrower.publicKey.toBuffer(), modelPda.toBuffer()],
+                scoreProgram.programId
+            );
+            await scoreProgram.methods
+                .postSubjectScore(600, 3, 400, 500, new BN(expiryTs), new BN(1), [
+                    { signature: Array.from(sig.signature), recoveryId: sig.recid },
+                ])
+                .accountsStrict({
+                    config: scoreConfigPda,
+                    subject: borrower.publicKey,
+                    model: modelPda,
+                    attestor: attestor.publicKey,
+                    score: scorePda,
+                    history: historyPdaOf(borrower.publicKey),
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([attestor])
+                .rpc();
+            return scorePda;
+        }
+
+        async function expectFundError(lenderAta: PublicKey, subjectScore: PublicKey | null, code: string) {
+            try {
+                await fundLoan(lender1, lenderAta, PARTIAL_AMOUNT, subjectScore);
+                expect.fail("Should have failed");
+            } catch (error) {
+                expect(error.error.errorCode.code).to.equal(code);
+            }
+        }
+
+        before(async () => {
+            await airdrop(attestor.publicKey);
+
+            if (!(await scoreProgram.account.config.fetchNullable(scoreConfigPda))) {
+                await scoreProgram.methods
+                    .initializeConfig(attestor.publicKey, 1, new BN(DAY_SECONDS), new BN(7 * DAY_SECONDS))
+                    .accountsPartial({ ...scoreAdmin, systemProgram: anchor.web3.SystemProgram.programId })
+                    .rpc();
+            } else {
+                await scoreProgram.methods.setIssuer(attestor.publicKey).accounts(scoreAdmin).rpc();
+                await scoreProgram.methods
+                    .setScoreWindows(new BN(DAY_SECONDS), new BN(7 * DAY_SECONDS))
+                    .accounts(scoreAdmin)
+                    .rpc();
+            }
+            await scoreProgram.methods.addOracle(Array.from(oraclePubkey)).accounts(scoreAdmin).rpc();
+            await scoreProgram.methods.setOracleThreshold(1).accounts(scoreAdmin).rpc();
+
+            const modelId = anchor.web3.Keypair.generate().publicKey.toBuffer();
+            const versionBuf = Buffer.alloc(2);
+            versionBuf.writeUInt16LE(1);
+            [modelPda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("model"), modelId, versionBuf],
+                scoreProgram.programId
+            );
+            await scoreProgram.methods
+                .addModel(Array.from(modelId), 1, 6, 0, 1000, Array.from(Buffer.alloc(32, 7)))
+                .accountsPartial({ ...scoreAdmin, model: modelPda })
+                .rpc();
+
+            await program.methods
+                .setScoreModels([modelPda])
+                .accountsPartial({ config: configPda, admin: provider.wallet.publicKey })
+                .rpc();
+        });
+
+        after(async () => {
+            await program.methods
+                .setScoreModels([])
+                .accountsPartial({ config: configPda, admin: provider.wallet.publicKey })
+                .rpc();
+        });
+
+        it("accepts a valid loan-specific score", async () => {
+            await createTestLoan();
+            const lender1Ata = await setupLender(lender1);
+            await postLoanScore();
+
+            await fundLoan(lender1, lender1Ata, PARTIAL_AMOUNT);
+            const loan = await program.account.loanAccount.fetch(loanPda);
+            expect(loan.fundedAmount.eq(PARTIAL_AMOUNT)).to.be.true;
+        });
+
+        it("falls back to the subject-level score only without a loan score", async () => {
+            await createTestLoan();
+            const lender1Ata = await setupLender(lender1);
+
+            await expectFundError(lender1Ata, null, "ScoreRequired");
+
+            const subjectScore = await postSubjectScore();
+            await fundLoan(lender1, lender1Ata, PARTIAL_AMOUNT, subjectScore);
+            const loan = await program.account.loanAccount.fetch(loanPda);
+            expect(loan.fundedAmount.eq(PARTIAL_AMOUNT)).to.be.true;
+        });
+
+        it("rejects a stale loan score even with a valid subject score", async () => {
+            await createTestLoan();
+            const lender1Ata = await setupLender(lender1);
+            const subjectScore = await postSubjectScore();
+            const loanScore = await postLoanScore();
+
+            await scoreProgram.methods
+                .revokeAttestation()
+                .accountsStrict({ ...scoreAdmin, subject: borrower.publicKey, loan: loanPda, score: loanScore })
+                .rpc();
+
+            await expectFundError(lender1Ata, subjectScore, "StaleScore");
+        });
+
+        it("rejects a disputed loan score even with a valid subject score", async () => {
+            await createTestLoan();
+            const lender1Ata = await setupLender(lender1);
+            const subjectScore = await postSubjectScore();
+            const loanScore = await postLoanScore();
+
+            await scoreProgram.methods
+                .disputeScore(Array.from(Buffer.from(sha3.keccak_256.digest(Buffer.from("wrong income")))))
+                .accountsStrict({ config: scoreConfigPda, subject: borrower.publicKey, score: loanScore })
+                .signers([borrower])
+                .rpc();
+
+            await expectFundError(lender1Ata, subjectScore, "ScoreDisputed");
+        });
+
+        it("only takes the loan score from its own PDA", async () => {
+            await createTestLoan();
+            const lender1Ata = await setupLender(lender1);
+            const subjectScore = await postSubjectScore();
+            const [lenderSharePda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("lender_share"), loanPda.toBuffer(), lender1.publicKey.toBuffer()],
+                program.programId
+            );
+
+            try {
+                await program.methods
+                    .lenderFund(PARTIAL_AMOUNT)
+                    .accountsStrict({
+                        config: configPda,
+                        lender: lender1.publicKey,
+                        loan: loanPda,
+                        loanSigner: loanSignerPda,
+                        lenderAta: lender1Ata,
+                        loanEscrowAta,
+                        lenderShare: lenderSharePda,
+                        score: subjectScore,
+                        subjectScore: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
+                        tokenProgram: TOKEN_PROGRAM_ID
+                    })
+                    .signers([lender1])
+                    .rpc();
+                expect.fail("Should have failed");
+            } catch (error) {
+                expect(error.error.errorCode.code).to.equal("ConstraintSeeds");
+            }
+        });
     });
 
     describe("Edge Cases", () => {
@@ -1,209 +1,224 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program, AnchorProvider } from "@coral-xyz/anchor";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { expect } from "chai";
+import * as secp256k1 from "secp256k1";
+import { Wallet } from "ethers";
 import { ScoreAttestor } from "../../target/types/score_attestor";
-import BN from "bn.js";
 
-describe("admin_only", () => {
+describe("score_attestor — admin_only", () => {
     const provider = AnchorProvider.env();
     anchor.setProvider(provider);
     const program = anchor.workspace.ScoreAttestor as Program<ScoreAttestor>;
 
-    let configPda: PublicKey;
-    let bump: number;
-    let newAdmin: Keypair;
-
-    const toModelIdBuffer = (m: any): Buffer => {
-        const idObj = m.modelId ?? m;
-        if (!idObj) throw new Error("Missing modelId");
-        if (Array.isArray(idObj?.["0"])) return Buffer.from(idObj["0"] as number[]);
-        if (Array.isArray(idObj)) return Buffer.from(idObj as number[]);
-        if (idObj instanceof Uint8Array) return Buffer.from(idObj as Uint8Array);
-        throw new Error("Unexpected ModelId shape");
-    };
+    const [configPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("score_config")],
+        program.programId
+    );
+    // The config is shared by every suite; each one leaves the provider wallet as its admin
+    const adminOnly = { config: configPda, admin: provider.wallet.publicKey };
+
+    async function airdrop(pubkey: PublicKey, sol = 2) {
+        const sig = await provider.connection.requestAirdrop(pubkey, sol * LAMPORTS_PER_SOL);
+        const { blockhash, lastValidBlockHeight } = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({ signature: sig, blockhash, lastValidBlockHeight }, "confirmed");
+        return sig;
+    }
+
+    function newOraclePubkey() {
+        const key = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        return Array.from(secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(key), false));
+    }
 
     before(async () => {
-        [configPda, bump] = PublicKey.findProgramAddressSync(
-            [Buffer.from("score_config")],
-            program.programId
-        );
-
-        await program.methods
-            .initializeConfig(3, new BN(3600))
-            .accountsStrict({
-                config: configPda,
-                admin: provider.wallet.publicKey,
-                systemProgram: SystemProgram.programId,
-            })
-            .rpc();
-    });
-
-    describe("Admin Changes", () => {
-        it("sets a new admin", async () => {
-            newAdmin = Keypair.generate();
-
+        if (!(await program.account.config.fetchNullable(configPda))) {
             await program.methods
-                .setAdmin(newAdmin.publicKey)
-                .accounts({
-                    config: configPda,
-                    admin: provider.wallet.publicKey,
-                })
+                .initializeConfig(anchor.web3.Keypair.generate().publicKey, 1, new BN(86_400), new BN(7 * 86_400))
+                .accountsPartial({ ...adminOnly, systemProgram: anchor.web3.SystemProgram.programId })
                 .rpc();
+        }
+    });
 
-            const config = await program.account.config.fetch(configPda);
-            expect(config.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
-        });
-
-        it("pauses and unpauses the contract", async () => {
+    it("initializes once and hands admin over in two steps", async () => {
+        const intruder = anchor.web3.Keypair.generate();
+        await airdrop(intruder.publicKey, 2);
+        try {
             await program.methods
-                .setPaused(true)
-                .accounts({
+                .initializeConfig(intruder.publicKey, 1, new BN(3600), new BN(3600))
+                .accountsPartial({
                     config: configPda,
-                    admin: newAdmin.publicKey,
+                    admin: intruder.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
                 })
-                .signers([newAdmin])
+                .signers([intruder])
                 .rpc();
-
-            let config = await program.account.config.fetch(configPda);
-            expect(config.paused).to.be.true;
-
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("already in use");
+        }
+
+        try {
+            await program.methods.proposeAdmin(PublicKey.default).accounts(adminOnly).rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidAdmin");
+        }
+
+        const newAdmin = anchor.web3.Keypair.generate();
+        await program.methods.proposeAdmin(newAdmin.publicKey).accounts(adminOnly).rpc();
+
+        let config = await program.account.config.fetch(configPda);
+        expect(config.admin.equals(provider.wallet.publicKey)).to.be.true;
+        expect(config.pendingAdmin.equals(newAdmin.publicKey)).to.be.true;
+
+        try {
             await program.methods
-                .setPaused(false)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
+                .acceptAdmin()
+                .accountsStrict({ config: configPda, newAdmin: intruder.publicKey })
+                .signers([intruder])
                 .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("NotPendingAdmin");
+        }
 
-            config = await program.account.config.fetch(configPda);
-            expect(config.paused).to.be.false;
-        });
-    });
+        await program.methods
+            .acceptAdmin()
+            .accountsStrict({ config: configPda, newAdmin: newAdmin.publicKey })
+            .signers([newAdmin])
+            .rpc();
 
-    describe("Oracles", () => {
-        it("adds and removes an oracle", async () => {
-            const oracle = Keypair.generate().publicKey;
+        config = await program.account.config.fetch(configPda);
+        expect(config.admin.equals(newAdmin.publicKey)).to.be.true;
+        expect(config.pendingAdmin).to.be.null;
 
-            await program.methods
-                .addOracle(oracle)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
-                .rpc();
+        // The previous admin has lost its rights
+        try {
+            await program.methods.setPaused(true).accounts(adminOnly).rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("ConstraintHasOne");
+        }
 
-            let config = await program.account.config.fetch(configPda);
-            expect(config.oracles.map((o: PublicKey) => o.toBase58())).to.include(
-                oracle.toBase58()
-            );
+        await program.methods
+            .proposeAdmin(provider.wallet.publicKey)
+            .accounts({ config: configPda, admin: newAdmin.publicKey })
+            .signers([newAdmin])
+            .rpc();
+        await program.methods
+            .acceptAdmin()
+            .accountsStrict({ config: configPda, newAdmin: provider.wallet.publicKey })
+            .rpc();
+        config = await program.account.config.fetch(configPda);
+        expect(config.admin.equals(provider.wallet.publicKey)).to.be.true;
+    });
 
-            await program.methods
-                .removeOracle(oracle)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
-                .rpc();
+    it("pauses and unpauses admin changes", async () => {
+        await program.methods.setPaused(true).accounts(adminOnly).rpc();
+        expect((await program.account.config.fetch(configPda)).paused).to.be.true;
 
-            config = await program.account.config.fetch(configPda);
-            expect(config.oracles.map((o: PublicKey) => o.toBase58())).to.not.include(
-                oracle.toBase58()
-            );
-        });
+        try {
+            await program.methods.addOracle(newOraclePubkey()).accounts(adminOnly).rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("Paused");
+        } finally {
+            await program.methods.setPaused(false).accounts(adminOnly).rpc();
+        }
 
-        it("sets oracle threshold", async () => {
-            const config = await program.account.config.fetch(configPda);
-            if (config.oracles.length === 0) {
-                const oracle = Keypair.generate().publicKey;
-                await program.methods
-                    .addOracle(oracle)
-                    .accounts({
-                        config: configPda,
-                        admin: newAdmin.publicKey,
-                    })
-                    .signers([newAdmin])
-                    .rpc();
+        expect((await program.account.config.fetch(configPda)).paused).to.be.false;
+    });
+
+    it("adds and removes oracles within the threshold", async () => {
+        const first = newOraclePubkey();
+        const second = newOraclePubkey();
+        await program.methods.addOracle(first).accounts(adminOnly).rpc();
+        await program.methods.addOracle(second).accounts(adminOnly).rpc();
+
+        const expectError = async (call: Promise<unknown>, code: string) => {
+            try {
+                await call;
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include(code);
             }
+        };
 
-            const updatedConfig = await program.account.config.fetch(configPda);
-            const threshold = Math.min(1, updatedConfig.oracles.length);
+        await expectError(program.methods.addOracle(first).accounts(adminOnly).rpc(), "OracleExists");
+        // Only uncompressed keys are accepted
+        await expectError(
+            program.methods.addOracle([2, ...first.slice(1)]).accounts(adminOnly).rpc(),
+            "InvalidParam"
+        );
 
-            await program.methods
-                .setOracleThreshold(threshold)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
-                .rpc();
+        const oracleCount = (await program.account.config.fetch(configPda)).oracles.length;
+        await expectError(program.methods.setOracleThreshold(0).accounts(adminOnly).rpc(), "InvalidOracleThreshold");
+        await expectError(
+            program.methods.setOracleThreshold(oracleCount + 1).accounts(adminOnly).rpc(),
+            "InvalidOracleThreshold"
+        );
 
-            const finalConfig = await program.account.config.fetch(configPda);
-            expect(finalConfig.oracleThreshold).to.equal(threshold);
-        });
-    });
+        // Removing an oracle can't leave fewer oracles than the threshold
+        await program.methods.setOracleThreshold(oracleCount).accounts(adminOnly).rpc();
+        await expectError(program.methods.removeOracle(second).accounts(adminOnly).rpc(), "InvalidOracleThreshold");
 
-    describe("Models", () => {
-        it("adds a model and changes its status", async () => {
-            const modelIdBytes = Buffer.alloc(32, 1);
-            const modelIdWrapper = { 0: Array.from(modelIdBytes) };
-            const version = 1;
+        await program.methods.setOracleThreshold(1).accounts(adminOnly).rpc();
+        await program.methods.removeOracle(second).accounts(adminOnly).rpc();
+        await expectError(program.methods.removeOracle(second).accounts(adminOnly).rpc(), "OracleNotFound");
 
-            await program.methods
-                .addModel(modelIdWrapper as any, version)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
-                .rpc();
-
-            let config = await program.account.config.fetch(configPda);
-            let model = config.models.find(
-                (m: any) =>
-                    toModelIdBuffer(m).equals(modelIdBytes) && m.version === version
-            );
-            expect(model).to.exist;
-            expect(model.enabled).to.be.true;
+        const config = await program.account.config.fetch(configPda);
+        expect(config.oracleThreshold).to.equal(1);
+        expect(config.oracles.some((o) => Buffer.from(o).equals(Buffer.from(first)))).to.be.true;
+        expect(config.oracles.some((o) => Buffer.from(o).equals(Buffer.from(second)))).to.be.false;
+    });
 
-            await program.methods
-                .setModelStatus(modelIdWrapper as any, version, false)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
-                .rpc();
+    it("sets the score validity and staleness windows", async () => {
+        for (const [validity, staleness, code] of [
+            [0, 3600, "InvalidExpiry"],
+            [3600, 0, "InvalidMaxStaleness"],
+        ] as const) {
+            try {
+                await program.methods
+                    .setScoreWindows(new BN(validity), new BN(staleness))
+                    .accounts(adminOnly)
+                    .rpc();
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include(code);
+            }
+        }
 
-            config = await program.account.config.fetch(configPda);
-            model = config.models.find(
-                (m: any) =>
-                    toModelIdBuffer(m).equals(modelIdBytes) && m.version === version
-            );
-            expect(model.enabled).to.be.false;
-        });
+        await program.methods.setScoreWindows(new BN(2 * 86_400), new BN(3 * 86_400)).accounts(adminOnly).rpc();
+        const config = await program.account.config.fetch(configPda);
+        expect(config.maxValiditySecs.toNumber()).to.equal(2 * 86_400);
+        expect(config.maxStalenessSecs.toNumber()).to.equal(3 * 86_400);
     });
 
-    describe("Max Staleness", () => {
-        it("sets max staleness", async () => {
-            const maxStalenessSecs = new BN(7200);
+    it("rejects models whose grades don't fit their score range", async () => {
+        const modelId = anchor.web3.Keypair.generate().publicKey.toBuffer();
+        const versionBuf = Buffer.alloc(2);
+        versionBuf.writeUInt16LE(1);
+        const [modelPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("model"), modelId, versionBuf],
+            program.programId
+        );
 
+        try {
             await program.methods
-                .setMaxStaleness(maxStalenessSecs)
-                .accounts({
-                    config: configPda,
-                    admin: newAdmin.publicKey,
-                })
-                .signers([newAdmin])
+                .addModel(Array.from(modelId), 1, 6, 100, 103, Array.from(Buffer.alloc(32, 7)))
+                .accountsPartial({ ...adminOnly, model: modelPda })
                 .rpc();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidParam");
+        }
 
-            const config = await program.account.config.fetch(configPda);
-            expect(config.maxStalenessSecs.toNumber()).to.equal(
-                maxStalenessSecs.toNumber()
-            );
-        });
+        await program.methods
+            .addModel(Array.from(modelId), 1, 6, 100, 900, Array.from(Buffer.alloc(32, 7)))
+            .accountsPartial({ ...adminOnly, model: modelPda })
+            .rpc();
+        const model = await program.account.scoringModel.fetch(modelPda);
+        expect(model.enabled).to.be.true;
+        expect(model.gradeScale).to.equal(6);
     });
-});
\ No newline at end of file
+});
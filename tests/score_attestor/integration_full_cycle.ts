@@ -2,6 +2,9 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
 import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { expect } from "chai";
+import * as secp256k1 from "secp256k1";
+import * as sha3 from "js-sha3";
+import { Wallet } from "ethers";
 import { ScoreAttestor } from "../../target/types/score_attestor";
 
 describe("score_attestor — integration full cycle", () => {
@@ -9,231 +12,235 @@ describe("score_attestor — integration full cycle", () => {
     anchor.setProvider(provider);
     const program = anchor.workspace.ScoreAttestor as Program<ScoreAttestor>;
 
+    const [configPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("score_config")],
+        program.programId
+    );
+    // The config is shared by every suite; each one leaves the provider wallet as its admin
+    const adminOnly = { config: configPda, admin: provider.wallet.publicKey };
+
+    const attestor = anchor.web3.Keypair.generate();
+    const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+    const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+    let modelPda: PublicKey;
+
+    type Fields = { score: number; grade: number; pdBps: number; recMinCollateralBps: number };
+    const defaults: Fields = { score: 720, grade: 4, pdBps: 450, recMinCollateralBps: 1500 };
+
     async function airdrop(pubkey: PublicKey, sol = 2) {
         const sig = await provider.connection.requestAirdrop(pubkey, sol * LAMPORTS_PER_SOL);
         const { blockhash, lastValidBlockHeight } = await provider.connection.getLatestBlockhash();
         await provider.connection.confirmTransaction({ signature: sig, blockhash, lastValidBlockHeight }, "confirmed");
     }
 
-    function toModelIdBuffer(m: any): Buffer {
-        const idObj = m.modelId ?? m;
-        if (Array.isArray(idObj?.["0"])) return Buffer.from(idObj["0"]);
-        if (Array.isArray(idObj)) return Buffer.from(idObj);
-        if (idObj instanceof Uint8Array) return Buffer.from(idObj);
-        throw new Error("Unexpected ModelId shape");
+    // Mirrors `score_message_hash` in post_scoreattestation.rs
+    function scoreMessage(subject: PublicKey, loan: PublicKey, f: Fields, expiryTs: number, nonce: number): Buffer {
+        const fields = Buffer.alloc(17);
+        fields.writeUInt16LE(f.score, 0);
+        fields.writeUInt8(f.grade, 2);
+        fields.writeUInt32LE(f.pdBps, 3);
+        fields.writeUInt16LE(f.recMinCollateralBps, 7);
+        fields.writeBigInt64LE(BigInt(expiryTs), 9);
+        const nonceBuf = Buffer.alloc(8);
+        nonceBuf.writeBigUInt64LE(BigInt(nonce));
+
+        return Buffer.from(
+            sha3.keccak_256.digest(
+                Buffer.concat([
+                    program.programId.toBuffer(),
+                    subject.toBuffer(),
+                    loan.toBuffer(),
+                    fields,
+                    modelPda.toBuffer(),
+                    nonceBuf,
+                ])
+            )
+        );
     }
 
-    async function setupConfig(admin: anchor.web3.Keypair, oracleThreshold = 3) {
-        const [configPda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("score_config")],
+    function scorePdaOf(subject: PublicKey, loan: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("score"), subject.toBuffer(), loan.toBuffer()],
             program.programId
-        );
+        )[0];
+    }
+
+    function historyPdaOf(subject: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("score_history"), subject.toBuffer()],
+            program.programId
+        )[0];
+    }
+
+    async function postScore(subject: PublicKey, loan: PublicKey, expiryTs: number, nonce: number, f: Fields = defaults) {
+        const sig = secp256k1.ecdsaSign(scoreMessage(subject, loan, f, expiryTs, nonce), oracleKey);
+        const scorePda = scorePdaOf(subject, loan);
 
         await program.methods
-            .initializeConfig(oracleThreshold, new BN(3600))
-            .accountsPartial({
+            .postScoreAttestation(
+                f.score,
+                f.grade,
+                f.pdBps,
+                f.recMinCollateralBps,
+                new BN(expiryTs),
+                new BN(nonce),
+                [{ signature: Array.from(sig.signature), recoveryId: sig.recid }]
+            )
+            .accountsStrict({
                 config: configPda,
-                admin: admin.publicKey,
+                subject,
+                loan,
+                model: modelPda,
+                attestor: attestor.publicKey,
+                score: scorePda,
+                history: historyPdaOf(subject),
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
-            .signers([admin])
+            .signers([attestor])
             .rpc();
 
-        // Ensure admin is an oracle
-        let cfg = await program.account.config.fetch(configPda);
-        if (!cfg.oracles.some((o: PublicKey) => o.equals(admin.publicKey))) {
-            await program.methods
-                .addOracle(admin.publicKey)
-                .accountsPartial({ config: configPda, admin: admin.publicKey })
-                .signers([admin])
-                .rpc();
+        return scorePda;
+    }
 
-            cfg = await program.account.config.fetch(configPda);
+    async function expectError(attempt: Promise<unknown>, code: string) {
+        try {
+            await attempt;
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include(code);
         }
+    }
 
-        // Add controlled additional oracles
-        const oracleKeypairs: anchor.web3.Keypair[] = [];
-        const neededAdditional = Math.max(0, (cfg.oracleThreshold as number) - 1);
-        while (oracleKeypairs.length < neededAdditional) {
-            const kp = anchor.web3.Keypair.generate();
-            await airdrop(kp.publicKey, 1);
-            await program.methods
-                .addOracle(kp.publicKey)
-                .accountsPartial({ config: configPda, admin: admin.publicKey })
-                .signers([admin])
-                .rpc();
-            oracleKeypairs.push(kp);
-        }
+    before(async () => {
+        await airdrop(attestor.publicKey);
 
-        // Ensure a model exists and is enabled (id = 0x01.., version = 1)
-        cfg = await program.account.config.fetch(configPda);
-        const modelIdBytes = new Uint8Array(32).fill(1);
-        const modelExists = cfg.models.some((m: any) => m.version === 1 && toModelIdBuffer(m).equals(Buffer.from(modelIdBytes)));
-        if (!modelExists) {
+        if (!(await program.account.config.fetchNullable(configPda))) {
             await program.methods
-                .addModel({ 0: Array.from(modelIdBytes) }, 1)
-                .accountsPartial({ config: configPda, admin: admin.publicKey })
-                .signers([admin])
+                .initializeConfig(attestor.publicKey, 1, new BN(86_400), new BN(7 * 86_400))
+                .accountsPartial({ ...adminOnly, systemProgram: anchor.web3.SystemProgram.programId })
                 .rpc();
+        } else {
+            await program.methods.setIssuer(attestor.publicKey).accounts(adminOnly).rpc();
+            await program.methods.setScoreWindows(new BN(86_400), new BN(7 * 86_400)).accounts(adminOnly).rpc();
         }
-
-        return { configPda, modelIdBytes, modelVersion: 1, oracleKeypairs };
-    }
-
-    function buildRemainingFromControlled(admin: anchor.web3.Keypair, oracleKeypairs: anchor.web3.Keypair[], count: number) {
-        const pubs: PublicKey[] = [admin.publicKey, ...oracleKeypairs.map(k => k.publicKey)];
-        const selected = pubs.slice(0, count);
-        return selected.map(pubkey => ({ pubkey, isSigner: true, isWritable: false }));
-    }
-
-    async function postScore(
-        admin: anchor.web3.Keypair,
-        prepared: { configPda: PublicKey; modelIdBytes: Uint8Array; modelVersion: number; oracleKeypairs: anchor.web3.Keypair[] },
-        subject: PublicKey,
-        loan: PublicKey,
-        expiryTs: number,
-        override?: { score?: number; grade?: number; pdBps?: number; recMinCollateralBps?: number; oracleSignerCount?: number }
-    ) {
-        const [scorePda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("score"), subject.toBuffer(), loan.toBuffer()],
+        await program.methods.addOracle(Array.from(oraclePubkey)).accounts(adminOnly).rpc();
+        await program.methods.setOracleThreshold(1).accounts(adminOnly).rpc();
+
+        const modelId = anchor.web3.Keypair.generate().publicKey.toBuffer();
+        const versionBuf = Buffer.alloc(2);
+        versionBuf.writeUInt16LE(1);
+        [modelPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("model"), modelId, versionBuf],
             program.programId
         );
-
-        const cfg = await program.account.config.fetch(prepared.configPda);
-        const threshold = cfg.oracleThreshold as number;
-        const needed = override?.oracleSignerCount ?? threshold;
-        const remaining = buildRemainingFromControlled(admin, prepared.oracleKeypairs, needed);
-
-        const tx = await program.methods
-            .postScoreAttestation(
-                { 0: Array.from(prepared.modelIdBytes) },
-                prepared.modelVersion,
-                { 0: Array.from(new Uint8Array(32).fill(7)) },
-                override?.score ?? 720,
-                override?.grade ?? 2,
-                override?.pdBps ?? 450,
-                override?.recMinCollateralBps ?? 1500,
-                new BN(expiryTs),
-                admin.publicKey
-            )
-            .accountsPartial({
-                config: prepared.configPda,
-                subject,
-                loan,
-                poster: admin.publicKey,
-                score: scorePda,
-                systemProgram: anchor.web3.SystemProgram.programId,
-            })
-            .remainingAccounts(remaining)
-            .signers([admin, ...prepared.oracleKeypairs.slice(0, Math.max(0, needed - 1))])
+        await program.methods
+            .addModel(Array.from(modelId), 1, 6, 0, 1000, Array.from(Buffer.alloc(32, 7)))
+            .accountsPartial({ ...adminOnly, model: modelPda })
             .rpc();
-
-        return { scorePda, tx };
-    }
+    });
 
     it("happy path: post -> revoke -> update expiry", async () => {
-        const admin = anchor.web3.Keypair.generate();
-        await airdrop(admin.publicKey, 2);
-
-        const prepared = await setupConfig(admin);
         const subject = anchor.web3.Keypair.generate().publicKey;
         const loan = anchor.web3.Keypair.generate().publicKey;
         const now = Math.floor(Date.now() / 1000);
-        const expiryTs = now + 3600;
 
-        const { scorePda } = await postScore(admin, prepared, subject, loan, expiryTs);
+        const scorePda = await postScore(subject, loan, now + 3600, 1);
         let score = await program.account.scoreAttestation.fetch(scorePda);
         expect(score.revoked).to.be.false;
-        expect(score.expiryTs.toNumber()).to.equal(expiryTs);
+        expect(score.expiryTs.toNumber()).to.equal(now + 3600);
 
         await program.methods
             .revokeAttestation()
-            .accountsPartial({
-                config: prepared.configPda,
-                admin: admin.publicKey,
-                subject,
-                loan,
-                score: scorePda,
-            })
-            .signers([admin])
+            .accountsStrict({ ...adminOnly, subject, loan, score: scorePda })
             .rpc();
-
         score = await program.account.scoreAttestation.fetch(scorePda);
         expect(score.revoked).to.be.true;
 
-        const newExpiry = now + 7200;
         await program.methods
-            .updateAttestationExpiry(new BN(newExpiry))
-            .accountsPartial({
-                config: prepared.configPda,
-                admin: admin.publicKey,
-                subject,
-                loan,
-                score: scorePda,
-            })
-            .signers([admin])
+            .updateAttestationExpiry(new BN(now + 7200))
+            .accountsStrict({ ...adminOnly, subject, loan, score: scorePda })
             .rpc();
-
         score = await program.account.scoreAttestation.fetch(scorePda);
-        expect(score.expiryTs.toNumber()).to.equal(newExpiry);
+        expect(score.expiryTs.toNumber()).to.equal(now + 7200);
         expect(score.revoked).to.be.true;
     });
 
-    it("pause blocks post and unpause allows post again", async () => {
-        const admin = anchor.web3.Keypair.generate();
-        await airdrop(admin.publicKey, 2);
-
-        const prepared = await setupConfig(admin);
+    it("pause blocks posting, disputes and closing until unpaused", async () => {
+        const subject = anchor.web3.Keypair.generate();
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const closedLoan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        const reasonHash = Array.from(Buffer.from(sha3.keccak_256.digest(Buffer.from("wrong income"))));
 
+        const disputedPda = scorePdaOf(subject.publicKey, loan);
+        const closedPda = await postScore(subject.publicKey, closedLoan, expiryTs, 1);
         await program.methods
-            .setPaused(true)
-            .accountsPartial({ config: prepared.configPda, admin: admin.publicKey })
-            .signers([admin])
+            .revokeAttestation()
+            .accountsStrict({ ...adminOnly, subject: subject.publicKey, loan: closedLoan, score: closedPda })
             .rpc();
 
-        const subject = anchor.web3.Keypair.generate().publicKey;
-        const loan = anchor.web3.Keypair.generate().publicKey;
-        const now = Math.floor(Date.now() / 1000);
+        const dispute = () =>
+            program.methods
+                .disputeScore(reasonHash)
+                .accountsStrict({ config: configPda, subject: subject.publicKey, score: disputedPda })
+                .signers([subject])
+                .rpc();
+        const close = () =>
+            program.methods
+                .closeScoreAttestation()
+                .accountsStrict({
+                    config: configPda,
+                    closer: attestor.publicKey,
+                    score: closedPda,
+                    payer: attestor.publicKey,
+                    history: historyPdaOf(subject.publicKey),
+                    loan: null,
+                })
+                .signers([attestor])
+                .rpc();
 
+        await program.methods.setPaused(true).accounts(adminOnly).rpc();
         try {
-            await postScore(admin, prepared, subject, loan, now + 3600);
-            expect.fail("Expected Paused error");
-        } catch (err) {
-            expect(err.toString()).to.include("Paused");
+            await expectError(postScore(subject.publicKey, loan, expiryTs, 1), "Paused");
+            await expectError(close(), "Paused");
+        } finally {
+            await program.methods.setPaused(false).accounts(adminOnly).rpc();
         }
 
-        await program.methods
-            .setPaused(false)
-            .accountsPartial({ config: prepared.configPda, admin: admin.publicKey })
-            .signers([admin])
-            .rpc();
+        await postScore(subject.publicKey, loan, expiryTs, 1);
+        await close();
+        expect(await provider.connection.getAccountInfo(closedPda)).to.be.null;
 
-        const { scorePda } = await postScore(admin, prepared, subject, loan, now + 7200);
-        const score = await program.account.scoreAttestation.fetch(scorePda);
-        expect(score.revoked).to.be.false;
-    });
+        await program.methods.setPaused(true).accounts(adminOnly).rpc();
+        try {
+            await expectError(dispute(), "Paused");
+        } finally {
+            await program.methods.setPaused(false).accounts(adminOnly).rpc();
+        }
 
-    it("re-post overwrites fields in same PDA", async () => {
-        const admin = anchor.web3.Keypair.generate();
-        await airdrop(admin.publicKey, 2);
+        await dispute();
+        expect((await program.account.scoreAttestation.fetch(disputedPda)).disputed).to.be.true;
+    });
 
-        const prepared = await setupConfig(admin);
+    it("re-post overwrites fields in the same PDA", async () => {
         const subject = anchor.web3.Keypair.generate().publicKey;
         const loan = anchor.web3.Keypair.generate().publicKey;
         const now = Math.floor(Date.now() / 1000);
 
-        const first = await postScore(admin, prepared, subject, loan, now + 3600, { score: 700, grade: 3, pdBps: 500, recMinCollateralBps: 1200 });
-        const before = await program.account.scoreAttestation.fetch(first.scorePda);
+        const scorePda = await postScore(subject, loan, now + 3600, 1, {
+            score: 700, grade: 4, pdBps: 500, recMinCollateralBps: 1200,
+        });
+        const before = await program.account.scoreAttestation.fetch(scorePda);
 
-        await postScore(admin, prepared, subject, loan, now + 7200, { score: 800, grade: 1, pdBps: 300, recMinCollateralBps: 900 });
-        const after = await program.account.scoreAttestation.fetch(first.scorePda);
+        await postScore(subject, loan, now + 7200, 2, { score: 800, grade: 4, pdBps: 300, recMinCollateralBps: 900 });
+        const after = await program.account.scoreAttestation.fetch(scorePda);
 
         expect(after.score).to.equal(800);
-        expect(after.grade).to.equal(1);
         expect(after.pdBps).to.equal(300);
         expect(after.recommendedMinCollateralBps).to.equal(900);
         expect(after.expiryTs.toNumber()).to.equal(now + 7200);
+        expect(after.nonce.toNumber()).to.equal(2);
         expect(before.subject.equals(after.subject)).to.be.true;
         expect(before.loan.equals(after.loan)).to.be.true;
     });
-});
\ No newline at end of file
+});
@@ -19,42 +19,167 @@ describe("score_attestor — AI scoring", () => {
         return sig;
     }
 
+    // The config can only be initialized once, so later tests take it over through the
+    // admin handover and reset its attestor, oracles and windows. Other suites leave the
+    // provider wallet as its admin.
+    let currentAdmin: anchor.web3.Keypair | null = null;
+
     async function initializeConfig(
         admin: anchor.web3.Keypair,
         attestor: PublicKey,
-        secp256k1Pubkey: Uint8Array
+        oraclePubkeys: Uint8Array[],
+        oracleThreshold = 1,
+        maxValiditySecs = 86_400,
+        maxStalenessSecs = 7 * 86_400
     ) {
         const [configPda] = PublicKey.findProgramAddressSync(
             [Buffer.from("score_config")],
             program.programId
         );
 
+        if (!(await program.account.config.fetchNullable(configPda))) {
+            await program.methods
+                .initializeConfig(attestor, oracleThreshold, new BN(maxValiditySecs), new BN(maxStalenessSecs))
+                .accountsPartial({
+                    config: configPda,
+                    admin: admin.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([admin])
+                .rpc();
+            currentAdmin = admin;
+
+            for (const oracle of oraclePubkeys) {
+                await program.methods
+                    .addOracle(Array.from(oracle))
+                    .accounts({ config: configPda, admin: admin.publicKey })
+                    .signers([admin])
+                    .rpc();
+            }
+
+            return configPda;
+        }
+
         await program.methods
-            .initializeConfig(attestor, Array.from(secp256k1Pubkey))
-            .accountsPartial({
-                config: configPda,
-                admin: admin.publicKey,
-                systemProgram: anchor.web3.SystemProgram.programId,
-            })
+            .proposeAdmin(admin.publicKey)
+            .accounts({ config: configPda, admin: currentAdmin?.publicKey ?? provider.wallet.publicKey })
+            .signers(currentAdmin ? [currentAdmin] : [])
+            .rpc();
+        await program.methods
+            .acceptAdmin()
+            .accountsStrict({ config: configPda, newAdmin: admin.publicKey })
+            .signers([admin])
+            .rpc();
+        currentAdmin = admin;
+
+        const adminOnly = { config: configPda, admin: admin.publicKey };
+        const previousOracles = (await program.account.config.fetch(configPda)).oracles;
+
+        await program.methods.setIssuer(attestor).accounts(adminOnly).signers([admin]).rpc();
+        for (const oracle of oraclePubkeys) {
+            await program.methods.addOracle(Array.from(oracle)).accounts(adminOnly).signers([admin]).rpc();
+        }
+        await program.methods.setOracleThreshold(1).accounts(adminOnly).signers([admin]).rpc();
+        for (const oracle of previousOracles) {
+            await program.methods.removeOracle(oracle).accounts(adminOnly).signers([admin]).rpc();
+        }
+        await program.methods.setOracleThreshold(oracleThreshold).accounts(adminOnly).signers([admin]).rpc();
+        await program.methods
+            .setScoreWindows(new BN(maxValiditySecs), new BN(maxStalenessSecs))
+            .accounts(adminOnly)
             .signers([admin])
             .rpc();
 
         return configPda;
     }
 
+    async function addModel(
+        admin: anchor.web3.Keypair,
+        configPda: PublicKey,
+        version = 1,
+        gradeScale = 6,
+        minScore = 0,
+        maxScore = 1000
+    ) {
+        const modelId = anchor.web3.Keypair.generate().publicKey.toBuffer();
+        const versionBuf = Buffer.alloc(2);
+        versionBuf.writeUInt16LE(version);
+        const [modelPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("model"), modelId, versionBuf],
+            program.programId
+        );
+
+        await program.methods
+            .addModel(Array.from(modelId), version, gradeScale, minScore, maxScore, Array.from(Buffer.alloc(32, 7)))
+            .accountsPartial({ config: configPda, admin: admin.publicKey, model: modelPda })
+            .signers([admin])
+            .rpc();
+
+        return modelPda;
+    }
+
+    // Mirrors `score_message_hash` in post_scoreattestation.rs
+    function scoreMessage(
+        subject: PublicKey,
+        loan: PublicKey,
+        score: number,
+        grade: number,
+        pdBps: number,
+        recMinCollateralBps: number,
+        expiryTs: number,
+        model: PublicKey,
+        nonce: number
+    ): Buffer {
+        const fields = Buffer.alloc(17);
+        fields.writeUInt16LE(score, 0);
+        fields.writeUInt8(grade, 2);
+        fields.writeUInt32LE(pdBps, 3);
+        fields.writeUInt16LE(recMinCollateralBps, 7);
+        fields.writeBigInt64LE(BigInt(expiryTs), 9);
+        const nonceBuf = Buffer.alloc(8);
+        nonceBuf.writeBigUInt64LE(BigInt(nonce));
+
+        return Buffer.from(
+            sha3.keccak_256.digest(
+                Buffer.concat([
+                    program.programId.toBuffer(),
+                    subject.toBuffer(),
+                    loan.toBuffer(),
+                    fields,
+                    model.toBuffer(),
+                    nonceBuf,
+                ])
+            )
+        );
+    }
+
+    function signMessage(message: Buffer, privateKeys: Buffer[]) {
+        return privateKeys.map((k) => {
+            const sig = secp256k1.ecdsaSign(message, k);
+            return { signature: sig.signature, recoveryId: sig.recid };
+        });
+    }
+
+    function historyPda(subject: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("score_history"), subject.toBuffer()],
+            program.programId
+        )[0];
+    }
+
     async function postScoreAttestation(
         attestor: anchor.web3.Keypair,
         configPda: PublicKey,
         subject: PublicKey,
         loan: PublicKey,
+        model: PublicKey,
         score: number,
         grade: number,
         pdBps: number,
         recMinCollateralBps: number,
         expiryTs: number,
-        message: Uint8Array,
-        signatureBytes: Uint8Array,
-        recoveryId: number
+        nonce: number,
+        signatures: { signature: Uint8Array; recoveryId: number }[]
     ) {
         const [scorePda] = PublicKey.findProgramAddressSync(
             [Buffer.from("score"), subject.toBuffer(), loan.toBuffer()],
@@ -68,16 +193,17 @@ describe("score_attestor — AI scoring", () => {
                 pdBps,
                 recMinCollateralBps,
                 new BN(expiryTs),
-                Array.from(message),
-                Array.from(signatureBytes),
-                recoveryId
+                new BN(nonce),
+                signatures.map((s) => ({ signature: Array.from(s.signature), recoveryId: s.recoveryId }))
             )
             .accountsStrict({
                 config: configPda,
                 subject,
                 loan,
+                model,
                 attestor: attestor.publicKey,
                 score: scorePda,
+                history: historyPda(subject),
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([attestor])
@@ -107,7 +233,8 @@ describe("score_attestor — AI scoring", () => {
         const now = Math.floor(Date.now() / 1000);
         const expiryTs = now + 3600;
 
-        const configPda = await initializeConfig(admin, attestor.publicKey, secp256k1PubKey);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [secp256k1PubKey]);
+        const modelPda = await addModel(admin, configPda);
 
         async function fetchVettorScore(address: string) {
             const url = "https://www.vettor.dev/api/wallet/analyze";
@@ -123,25 +250,25 @@ describe("score_attestor — AI scoring", () => {
             const data = await res.json() as any;
             return {
                 score: data.totalScore,
-                grade: data.totalScore > 700 ? 5 : data.totalScore > 500 ? 4 : data.totalScore > 350 ? 3 : data.totalScore > 200 ? 2 : 1,
+                // Band of the default test model: 6 grades over 0..=1000
+                grade: Math.floor((data.totalScore * 6) / 1001),
                 pdBps: Math.max(50, Math.min(5000, Math.floor((850 - data.totalScore) * 6))),
             };
         }
 
         const vettorScore = await fetchVettorScore(subject.publicKey.toBase58());
 
-        function toBigUInt64BE(value: number | bigint): Buffer {
-            const buf = Buffer.allocUnsafe(8);
-            buf.writeBigUInt64BE(BigInt(value));
-            return buf;
-        }
-
-        const messageData = Buffer.concat([
-            subject.publicKey.toBuffer(),
-            toBigUInt64BE(vettorScore.score),
-            toBigUInt64BE(now),
-        ]);
-        const plaintextHash = Buffer.from(sha3.keccak_256.digest(messageData));
+        const plaintextHash = scoreMessage(
+            subject.publicKey,
+            loan,
+            vettorScore.score,
+            vettorScore.grade,
+            vettorScore.pdBps,
+            500,
+            expiryTs,
+            modelPda,
+            1
+        );
 
         const sigObj = secp256k1.ecdsaSign(plaintextHash, secp256k1PrivKey);
         const signatureBytes = sigObj.signature;
@@ -154,14 +281,14 @@ describe("score_attestor — AI scoring", () => {
             configPda,
             subject.publicKey,
             loan,
+            modelPda,
             vettorScore.score,
             vettorScore.grade,
             vettorScore.pdBps,
             500,
             expiryTs,
-            plaintextHash,
-            signatureBytes,
-            recoveryId
+            1,
+            [{ signature: signatureBytes, recoveryId }]
         );
 
         const scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
@@ -172,7 +299,471 @@ describe("score_attestor — AI scoring", () => {
         expect(scoreAcc.recommendedMinCollateralBps).to.equal(500);
         expect(scoreAcc.expiryTs.toNumber()).to.equal(expiryTs);
         expect(scoreAcc.revoked).to.be.false;
+        expect(scoreAcc.model.equals(modelPda)).to.be.true;
+        expect(scoreAcc.modelVersion).to.equal(1);
 
         console.log("Posted attestation with Vettor AI score successfully. TX:", tx);
     });
-});
\ No newline at end of file
+
+    it("requires a threshold of distinct oracle signatures", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKeys = [Wallet.createRandom(), Wallet.createRandom()].map((w) =>
+            Buffer.from(w.privateKey.slice(2), "hex")
+        );
+        const oraclePubkeys = oracleKeys.map((k) =>
+            secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(k), false)
+        );
+        const configPda = await initializeConfig(admin, attestor.publicKey, oraclePubkeys, 2);
+        const modelPda = await addModel(admin, configPda);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        const message = scoreMessage(subject, loan, 700, 4, 300, 500, expiryTs, modelPda, 1);
+        const signatures = signMessage(message, oracleKeys);
+
+        try {
+            // The same oracle twice only counts once
+            await postScoreAttestation(attestor, configPda, subject, loan, modelPda, 700, 4, 300, 500, expiryTs, 1, [
+                signatures[0],
+                signatures[0],
+            ]);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InsufficientOracleSigners");
+        }
+
+        const { scorePda } = await postScoreAttestation(
+            attestor, configPda, subject, loan, modelPda, 700, 4, 300, 500, expiryTs, 1, signatures
+        );
+
+        const scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.score).to.equal(700);
+    });
+
+    it("only accepts scores from enabled models", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda, 2);
+
+        const model = await program.account.scoringModel.fetch(modelPda);
+        expect(model.version).to.equal(2);
+        expect(model.enabled).to.be.true;
+
+        await program.methods
+            .setModelStatus(false)
+            .accountsPartial({ config: configPda, admin: admin.publicKey, model: modelPda })
+            .signers([admin])
+            .rpc();
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        const message = scoreMessage(subject, loan, 700, 4, 300, 500, expiryTs, modelPda, 1);
+
+        try {
+            await postScoreAttestation(
+                attestor, configPda, subject, loan, modelPda, 700, 4, 300, 500, expiryTs, 1,
+                signMessage(message, [oracleKey])
+            );
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("ModelNotAllowed");
+        }
+    });
+
+    it("binds the signature to the posted fields and nonce", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        const signatures = signMessage(
+            scoreMessage(subject, loan, 600, 3, 400, 500, expiryTs, modelPda, 1),
+            [oracleKey]
+        );
+
+        try {
+            // Same signature, inflated score
+            await postScoreAttestation(
+                attestor, configPda, subject, loan, modelPda, 650, 3, 400, 500, expiryTs, 1, signatures
+            );
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("UnauthorizedOracle");
+        }
+
+        await postScoreAttestation(
+            attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, expiryTs, 1, signatures
+        );
+
+        try {
+            await postScoreAttestation(
+                attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, expiryTs, 1, signatures
+            );
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidNonce");
+        }
+    });
+
+    it("bounds expiry by the validity window and stamps the staleness deadline", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey], 1, 3600, 600);
+        const modelPda = await addModel(admin, configPda);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const tooFar = Math.floor(Date.now() / 1000) + 2 * 3600;
+
+        try {
+            await postScoreAttestation(
+                attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, tooFar, 1,
+                signMessage(scoreMessage(subject, loan, 600, 3, 400, 500, tooFar, modelPda, 1), [oracleKey])
+            );
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidExpiry");
+        }
+
+        const expiryTs = Math.floor(Date.now() / 1000) + 1800;
+        const { scorePda } = await postScoreAttestation(
+            attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, expiryTs, 1,
+            signMessage(scoreMessage(subject, loan, 600, 3, 400, 500, expiryTs, modelPda, 1), [oracleKey])
+        );
+
+        const scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.staleAt.toNumber()).to.equal(scoreAcc.postedAt.toNumber() + 600);
+    });
+
+    it("posts a subject-level score seeded by model", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        // Subject-level scores sign over the default pubkey as loan
+        const message = scoreMessage(subject, PublicKey.default, 720, 4, 250, 500, expiryTs, modelPda, 1);
+        const [scorePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("score"), subject.toBuffer(), modelPda.toBuffer()],
+            program.programId
+        );
+
+        await program.methods
+            .postSubjectScore(
+                720,
+                4,
+                250,
+                500,
+                new BN(expiryTs),
+                new BN(1),
+                signMessage(message, [oracleKey]).map((s) => ({
+                    signature: Array.from(s.signature),
+                    recoveryId: s.recoveryId,
+                }))
+            )
+            .accountsStrict({
+                config: configPda,
+                subject,
+                model: modelPda,
+                attestor: attestor.publicKey,
+                score: scorePda,
+                history: historyPda(subject),
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([attestor])
+            .rpc();
+
+        const scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.score).to.equal(720);
+        expect(scoreAcc.loan.equals(PublicKey.default)).to.be.true;
+        expect(scoreAcc.model.equals(modelPda)).to.be.true;
+    });
+
+    it("keeps a per-subject history of posted scores", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+
+        for (const [nonce, score] of [[1, 610], [2, 640]]) {
+            await postScoreAttestation(
+                attestor, configPda, subject, loan, modelPda, score, 3, 400, 500, expiryTs, nonce,
+                signMessage(scoreMessage(subject, loan, score, 3, 400, 500, expiryTs, modelPda, nonce), [oracleKey])
+            );
+        }
+
+        const history = await program.account.scoreHistory.fetch(historyPda(subject));
+        expect(history.subject.equals(subject)).to.be.true;
+        expect(history.totalPosted.toNumber()).to.equal(2);
+        expect(history.entries.map((e: any) => e.score)).to.deep.equal([610, 640]);
+        expect(history.entries[1].model.equals(modelPda)).to.be.true;
+    });
+
+    it("validates score fields against the model", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda, 1, 5, 300, 849);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+
+        // [score, grade, pdBps]: below range, wrong band, pd above 100%
+        for (const [score, grade, pdBps] of [[250, 0, 400], [700, 1, 400], [700, 3, 10_001]]) {
+            try {
+                await postScoreAttestation(
+                    attestor, configPda, subject, loan, modelPda, score, grade, pdBps, 500, expiryTs, 1,
+                    signMessage(scoreMessage(subject, loan, score, grade, pdBps, 500, expiryTs, modelPda, 1), [oracleKey])
+                );
+                expect.fail("Should have failed");
+            } catch (err: any) {
+                expect(err.toString()).to.include("InvalidParam");
+            }
+        }
+
+        // 700 falls in band 3 of five 110-point bands starting at 300
+        const { scorePda } = await postScoreAttestation(
+            attestor, configPda, subject, loan, modelPda, 700, 3, 400, 500, expiryTs, 1,
+            signMessage(scoreMessage(subject, loan, 700, 3, 400, 500, expiryTs, modelPda, 1), [oracleKey])
+        );
+        const scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.grade).to.equal(3);
+    });
+
+    it("lets the attestor close revoked scores and refunds the payer", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+        const stranger = anchor.web3.Keypair.generate();
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda);
+
+        const subject = anchor.web3.Keypair.generate().publicKey;
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        const { scorePda } = await postScoreAttestation(
+            attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, expiryTs, 1,
+            signMessage(scoreMessage(subject, loan, 600, 3, 400, 500, expiryTs, modelPda, 1), [oracleKey])
+        );
+
+        const close = (closer: anchor.web3.Keypair) =>
+            program.methods
+                .closeScoreAttestation()
+                .accountsStrict({
+                    config: configPda,
+                    closer: closer.publicKey,
+                    score: scorePda,
+                    payer: attestor.publicKey,
+                    history: historyPda(subject),
+                    loan: null,
+                })
+                .signers([closer])
+                .rpc();
+
+        try {
+            await close(attestor);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("CloseNotAllowed");
+        }
+
+        await program.methods
+            .revokeAttestation()
+            .accountsStrict({ config: configPda, admin: admin.publicKey, subject, loan, score: scorePda })
+            .signers([admin])
+            .rpc();
+
+        try {
+            await close(stranger);
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("CloseNotAllowed");
+        }
+
+        const before = await provider.connection.getBalance(attestor.publicKey);
+        await close(attestor);
+        const after = await provider.connection.getBalance(attestor.publicKey);
+
+        expect(await provider.connection.getAccountInfo(scorePda)).to.be.null;
+        expect(after).to.be.greaterThan(before);
+
+        // The closed score's payload can't be replayed into a fresh account
+        try {
+            await postScoreAttestation(
+                attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, expiryTs, 1,
+                signMessage(scoreMessage(subject, loan, 600, 3, 400, 500, expiryTs, modelPda, 1), [oracleKey])
+            );
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("InvalidNonce");
+        }
+
+        await postScoreAttestation(
+            attestor, configPda, subject, loan, modelPda, 600, 3, 400, 500, expiryTs, 2,
+            signMessage(scoreMessage(subject, loan, 600, 3, 400, 500, expiryTs, modelPda, 2), [oracleKey])
+        );
+        const history = await program.account.scoreHistory.fetch(historyPda(subject));
+        expect(history.nonceFloors.length).to.equal(1);
+        expect(history.nonceFloors[0].scope.toBase58()).to.equal(loan.toBase58());
+        expect(history.nonceFloors[0].nonce.toNumber()).to.equal(1);
+    });
+
+    it("freezes a disputed score until the dispute is resolved", async () => {
+        const admin = anchor.web3.Keypair.generate();
+        await airdrop(admin.publicKey, 2);
+        const attestor = anchor.web3.Keypair.generate();
+        await airdrop(attestor.publicKey, 2);
+        const subject = anchor.web3.Keypair.generate();
+
+        const oracleKey = Buffer.from(Wallet.createRandom().privateKey.slice(2), "hex");
+        const oraclePubkey = secp256k1.publicKeyConvert(secp256k1.publicKeyCreate(oracleKey), false);
+        const configPda = await initializeConfig(admin, attestor.publicKey, [oraclePubkey]);
+        const modelPda = await addModel(admin, configPda);
+
+        const loan = anchor.web3.Keypair.generate().publicKey;
+        const expiryTs = Math.floor(Date.now() / 1000) + 3600;
+        const sign = (score: number, grade: number, nonce: number) =>
+            signMessage(
+                scoreMessage(subject.publicKey, loan, score, grade, 400, 500, expiryTs, modelPda, nonce),
+                [oracleKey]
+            );
+
+        const { scorePda } = await postScoreAttestation(
+            attestor, configPda, subject.publicKey, loan, modelPda, 400, 2, 400, 500, expiryTs, 1, sign(400, 2, 1)
+        );
+
+        const reasonHash = Buffer.from(sha3.keccak_256.digest(Buffer.from("income not counted")));
+        await program.methods
+            .disputeScore(Array.from(reasonHash))
+            .accountsStrict({ config: configPda, subject: subject.publicKey, score: scorePda })
+            .signers([subject])
+            .rpc();
+
+        let scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.disputed).to.be.true;
+        expect(Buffer.from(scoreAcc.disputeReasonHash).equals(reasonHash)).to.be.true;
+
+        try {
+            await postScoreAttestation(
+                attestor, configPda, subject.publicKey, loan, modelPda, 400, 2, 400, 500, expiryTs, 2, sign(400, 2, 2)
+            );
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("ScoreDisputed");
+        }
+
+        await program.methods
+            .resolveDispute(
+                { amend: {} },
+                {
+                    score: 550,
+                    grade: 3,
+                    pdBps: 400,
+                    recommendedMinCollateralBps: 500,
+                    expiryTs: new BN(expiryTs),
+                    nonce: new BN(2),
+                },
+                sign(550, 3, 2).map((s) => ({ signature: Array.from(s.signature), recoveryId: s.recoveryId }))
+            )
+            .accountsStrict({
+                config: configPda,
+                resolver: admin.publicKey,
+                score: scorePda,
+                model: modelPda,
+                history: historyPda(subject.publicKey),
+            })
+            .signers([admin])
+            .rpc();
+
+        scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.disputed).to.be.false;
+        expect(scoreAcc.score).to.equal(550);
+        expect(scoreAcc.nonce.toNumber()).to.equal(2);
+
+        // The amended payload can be disputed once; upholding it needs no model or history
+        const dispute = () =>
+            program.methods
+                .disputeScore(Array.from(reasonHash))
+                .accountsStrict({ config: configPda, subject: subject.publicKey, score: scorePda })
+                .signers([subject])
+                .rpc();
+        await dispute();
+        await program.methods
+            .resolveDispute({ uphold: {} }, null, [])
+            .accountsStrict({
+                config: configPda,
+                resolver: attestor.publicKey,
+                score: scorePda,
+                model: null,
+                history: null,
+            })
+            .signers([attestor])
+            .rpc();
+
+        try {
+            await dispute();
+            expect.fail("Should have failed");
+        } catch (err: any) {
+            expect(err.toString()).to.include("AlreadyDisputed");
+        }
+
+        // So the attestor isn't held off from posting the next score
+        await postScoreAttestation(
+            attestor, configPda, subject.publicKey, loan, modelPda, 560, 3, 400, 500, expiryTs, 3, sign(560, 3, 3)
+        );
+        scoreAcc = await program.account.scoreAttestation.fetch(scorePda);
+        expect(scoreAcc.nonce.toNumber()).to.equal(3);
+        expect(scoreAcc.disputedNonce.toNumber()).to.equal(2);
+    });
+});